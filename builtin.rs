@@ -2,7 +2,7 @@
 // Homun Built-in — always included by the compiler
 // ============================================================
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 // ── Indexing: arr[i], dict[key] ─────────────────────────────
 
@@ -99,6 +99,18 @@ impl HomunContains<&str> for Vec<String> {
 impl<V> HomunContains<&str> for HashMap<String, V> {
     fn homun_contains(&self, item: &&str) -> bool { self.contains_key(*item) }
 }
+impl<T: PartialEq> HomunContains<T> for VecDeque<T> {
+    fn homun_contains(&self, item: &T) -> bool { self.contains(item) }
+}
+impl HomunContains<&str> for VecDeque<String> {
+    fn homun_contains(&self, item: &&str) -> bool { self.iter().any(|s| s == *item) }
+}
+impl<K: Ord, V> HomunContains<K> for BTreeMap<K, V> {
+    fn homun_contains(&self, item: &K) -> bool { self.contains_key(item) }
+}
+impl<V> HomunContains<&str> for BTreeMap<String, V> {
+    fn homun_contains(&self, item: &&str) -> bool { self.contains_key(*item) }
+}
 impl HomunContains<char> for str {
     fn homun_contains(&self, item: &char) -> bool { self.contains(*item) }
 }
@@ -8,12 +8,22 @@
 //   is_alnum("3")   // true
 //   is_digit("7")   // true
 //   is_ws(" ")      // true
+//   is_ws_unicode("\u{a0}")  // true; is_ws stays ASCII-only
+//   is_ident("_foo1")  // true: start rule, then continue rule
+//   is_punct("-->")    // true (ASCII); is_symbol("¿") also covers non-ASCII
+//   char_code("A")      // 65 (-1 for "")
+//   char_from_code(66)  // "B" ("" for invalid code points)
+//   escape_control("a\tb")  // "a\\tb" — safe to echo in error messages
+//   to_ascii_lossy("café")   // "cafe"; unmappable characters become "_"
+//   char_category("x")       // "alpha" | "digit" | "ws" | "punct" | "control" | "other"
+//   count_leading("    x", "ws")  // 4; count_class("a1b2", "digit") == 2
 //
 // Functions accept impl AsRef<str> so they work with both:
 //   - &str literals (used in Rust tests)
 //   - String values (emitted by homunc codegen for .hom string args,
 //     which always calls .to_string() on string literals)
-// Returns false for empty input.
+// The is_* classifiers return false for empty input, except is_ascii
+// (true for "", matching str::is_ascii).
 // ============================================================
 
 /// True if every character in `s` is alphabetic (Unicode).
@@ -40,6 +50,248 @@ pub fn is_ws(s: impl AsRef<str>) -> bool {
     !s.is_empty() && s.chars().all(|c| c.is_ascii_whitespace())
 }
 
+/// True if every character in `s` may start an identifier: `_` or an
+/// alphabetic character (Unicode, approximating XID_Start without a crate).
+pub fn is_ident_start(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && s.chars().all(ident_start_char)
+}
+
+/// True if every character in `s` may continue an identifier: `_` or an
+/// alphanumeric character (Unicode, approximating XID_Continue).
+pub fn is_ident_continue(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && s.chars().all(ident_continue_char)
+}
+
+/// True if `s` is a whole identifier: the first character passes
+/// `is_ident_start` and the rest pass `is_ident_continue`.
+pub fn is_ident(s: impl AsRef<str>) -> bool {
+    let mut chars = s.as_ref().chars();
+    match chars.next() {
+        Some(c) => ident_start_char(c) && chars.all(ident_continue_char),
+        None => false,
+    }
+}
+
+/// True if every character in `s` is ASCII punctuation
+/// (`!"#$%&'()*+,-./:;<=>?@[\]^_`{|}~`). Note `_` counts, as in Rust.
+pub fn is_punct(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_punctuation())
+}
+
+/// True if every character in `s` is punctuation or a symbol, ASCII or not
+/// (`¿`, `→`, `©`, ...). Without Unicode category tables this treats any
+/// non-ASCII character that is not alphanumeric, whitespace or control as
+/// a symbol.
+pub fn is_symbol(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty()
+        && s.chars().all(|c| {
+            c.is_ascii_punctuation()
+                || (!c.is_ascii() && !c.is_alphanumeric() && !c.is_whitespace() && !c.is_control())
+        })
+}
+
+/// Code point of the first character of `s` (like Python's `ord`), or -1
+/// for empty input.
+pub fn char_code(s: impl AsRef<str>) -> i32 {
+    s.as_ref().chars().next().map_or(-1, |c| c as i32)
+}
+
+/// The character with code point `code` as a String (like Python's `chr`).
+/// Returns "" for negative values, surrogates (U+D800–U+DFFF) and anything
+/// above U+10FFFF.
+pub fn char_from_code(code: i32) -> String {
+    u32::try_from(code)
+        .ok()
+        .and_then(char::from_u32)
+        .map_or(String::new(), String::from)
+}
+
+/// True if no character in `s` is a control character. Space counts as
+/// printable.
+pub fn is_printable(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && !s.chars().any(|c| c.is_control())
+}
+
+/// True if every character in `s` is a control character (C0, DEL or C1).
+pub fn is_control(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && s.chars().all(|c| c.is_control())
+}
+
+/// Replace control characters with escapes for error messages: `\n`, `\t`,
+/// `\r`, and `\xHH` for the rest (e.g. `\x07`, `\x7f`). Everything else,
+/// including non-ASCII text, is left alone.
+pub fn escape_control(s: impl AsRef<str>) -> String {
+    let mut out = String::new();
+    for c in s.as_ref().chars() {
+        match c {
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\x{:02x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// True if every character in `s` is Unicode whitespace (`char::is_whitespace`),
+/// e.g. U+00A0 no-break space or U+3000 ideographic space. `is_ws` stays
+/// ASCII-only, since existing lexers depend on that.
+pub fn is_ws_unicode(s: impl AsRef<str>) -> bool {
+    let s = s.as_ref();
+    !s.is_empty() && s.chars().all(|c| c.is_whitespace())
+}
+
+/// Replace every run of Unicode whitespace with a single ASCII space.
+/// Leading and trailing runs become one space too; nothing is trimmed.
+pub fn normalize_ws(s: impl AsRef<str>) -> String {
+    let mut out = String::new();
+    let mut in_ws = false;
+    for c in s.as_ref().chars() {
+        if c.is_whitespace() {
+            if !in_ws {
+                out.push(' ');
+            }
+            in_ws = true;
+        } else {
+            out.push(c);
+            in_ws = false;
+        }
+    }
+    out
+}
+
+/// True if every character in `s` is ASCII. Unlike the classifiers above,
+/// returns true for empty input, matching `str::is_ascii`.
+pub fn is_ascii(s: impl AsRef<str>) -> bool {
+    s.as_ref().is_ascii()
+}
+
+/// Transliterate `s` to ASCII for generated identifiers: accented Latin
+/// letters map to their base letters (é→e, ü→u, ß→ss), combining marks
+/// (U+0300–U+036F) are dropped, and anything else non-ASCII becomes `_`.
+pub fn to_ascii_lossy(s: impl AsRef<str>) -> String {
+    let mut out = String::new();
+    for c in s.as_ref().chars() {
+        if c.is_ascii() {
+            out.push(c);
+        } else if ('\u{300}'..='\u{36f}').contains(&c) {
+            continue;
+        } else {
+            out.push_str(latin_base(c).unwrap_or("_"));
+        }
+    }
+    out
+}
+
+/// Classify the first character of `s` with a single call, for lexer
+/// dispatch. Returns one of "alpha", "digit", "ws", "punct", "control" or
+/// "other" ("empty" for empty input). Each label agrees with the matching
+/// is_* function; ASCII whitespace such as `\t` is "ws", not "control".
+pub fn char_category(s: impl AsRef<str>) -> String {
+    let Some(c) = s.as_ref().chars().next() else {
+        return "empty".to_string();
+    };
+    let label = if c.is_alphabetic() {
+        "alpha"
+    } else if c.is_ascii_digit() {
+        "digit"
+    } else if c.is_ascii_whitespace() {
+        "ws"
+    } else if c.is_ascii_punctuation() {
+        "punct"
+    } else if c.is_control() {
+        "control"
+    } else {
+        "other"
+    };
+    label.to_string()
+}
+
+/// Number of characters at the start of `s` in `class_name` ("alpha",
+/// "digit", "ws" or "alnum", same rules as the is_* functions), e.g. the
+/// indentation depth of a line. Panics on an unknown class name.
+pub fn count_leading(s: impl AsRef<str>, class_name: impl AsRef<str>) -> i32 {
+    let pred = class_pred(class_name.as_ref());
+    s.as_ref().chars().take_while(|c| pred(*c)).count() as i32
+}
+
+/// Number of characters anywhere in `s` in `class_name` (see `count_leading`).
+/// Panics on an unknown class name.
+pub fn count_class(s: impl AsRef<str>, class_name: impl AsRef<str>) -> i32 {
+    let pred = class_pred(class_name.as_ref());
+    s.as_ref().chars().filter(|c| pred(*c)).count() as i32
+}
+
+fn class_pred(class_name: &str) -> fn(char) -> bool {
+    match class_name {
+        "alpha" => |c| c.is_alphabetic(),
+        "digit" => |c| c.is_ascii_digit(),
+        "ws" => |c| c.is_ascii_whitespace(),
+        "alnum" => |c| c.is_alphanumeric(),
+        other => panic!(
+            "unknown character class {:?}; expected one of \"alpha\", \"digit\", \"ws\", \"alnum\"",
+            other
+        ),
+    }
+}
+
+/// Base-letter spelling of a Latin-1 (plus a few Latin Extended-A) letter.
+fn latin_base(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Č' => "C",
+        'ç' | 'ć' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+        'Ì'..='Ï' | 'Ī' | 'Į' | 'İ' => "I",
+        'ì'..='ï' | 'ī' | 'į' | 'ı' => "i",
+        'Ł' => "L",
+        'ł' => "l",
+        'Ñ' | 'Ń' | 'Ň' => "N",
+        'ñ' | 'ń' | 'ň' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ř' => "R",
+        'ř' => "r",
+        'Ś' | 'Š' | 'Ş' => "S",
+        'ś' | 'š' | 'ş' => "s",
+        'ß' => "ss",
+        'Ť' | 'Ţ' => "T",
+        'ť' | 'ţ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù'..='ü' | 'ū' | 'ů' | 'ű' | 'ų' => "u",
+        'Ý' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+fn ident_start_char(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn ident_continue_char(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,4 +452,335 @@ mod tests {
         assert!(is_ws(" ".to_string()));
         assert!(!is_ws("a".to_string()));
     }
+
+    // ── is_ident_start / is_ident_continue / is_ident ───────
+    #[test]
+    fn test_is_ident_start() {
+        assert!(is_ident_start("_"));
+        assert!(is_ident_start("a"));
+        assert!(is_ident_start("_foo"));
+        assert!(!is_ident_start("1"));
+        assert!(!is_ident_start("a1"));
+        assert!(!is_ident_start(""));
+    }
+
+    #[test]
+    fn test_is_ident_continue() {
+        assert!(is_ident_continue("_"));
+        assert!(is_ident_continue("a1"));
+        assert!(is_ident_continue("1a"));
+        assert!(!is_ident_continue("a-b"));
+        assert!(!is_ident_continue(""));
+    }
+
+    #[test]
+    fn test_is_ident() {
+        assert!(is_ident("_foo"));
+        assert!(is_ident("a1"));
+        assert!(is_ident("_"));
+        assert!(!is_ident("1a"));
+        assert!(!is_ident("a b"));
+        assert!(!is_ident(""));
+    }
+
+    #[test]
+    fn test_is_ident_non_ascii() {
+        assert!(is_ident_start("é"));
+        assert!(is_ident("café"));
+        assert!(is_ident("名前"));
+        assert!(!is_ident("a·-"));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_is_ident_string_type() {
+        let s = String::from("node_1");
+        assert!(is_ident(s));
+    }
+
+    // ── is_punct / is_symbol ────────────────────────────────
+    #[test]
+    fn test_is_punct() {
+        assert!(is_punct("-->"));
+        assert!(is_punct("_"));
+        assert!(is_punct("{}"));
+        assert!(!is_punct("a-"));
+        assert!(!is_punct("- "));
+        assert!(!is_punct("¿"));
+        assert!(!is_punct(""));
+    }
+
+    #[test]
+    fn test_is_symbol() {
+        assert!(is_symbol("-->"));
+        assert!(is_symbol("¿"));
+        assert!(is_symbol("→©"));
+        assert!(is_symbol("_"));
+        assert!(!is_symbol("a-"));
+        assert!(!is_symbol("é"));
+        assert!(!is_symbol("\u{a0}"));
+        assert!(!is_symbol(""));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_is_punct_string_type() {
+        let s = String::from("==");
+        assert!(is_punct(s.clone()));
+        assert!(is_symbol(s));
+    }
+
+    // ── char_code / char_from_code ──────────────────────────
+    #[test]
+    fn test_char_code_ascii() {
+        assert_eq!(char_code("A"), 65);
+        assert_eq!(char_code("abc"), 97);
+        assert_eq!(char_code(""), -1);
+    }
+
+    #[test]
+    fn test_char_code_round_trip() {
+        for s in ["A", "z", "0", " ", "é", "名", "🦀"] {
+            assert_eq!(char_from_code(char_code(s)), s);
+        }
+        assert_eq!(char_code("é"), 0xE9);
+    }
+
+    #[test]
+    fn test_char_from_code_alphabet() {
+        let names: Vec<String> = (0..3).map(|i| char_from_code(char_code("A") + i)).collect();
+        assert_eq!(names, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_char_from_code_invalid() {
+        assert_eq!(char_from_code(-1), "");
+        assert_eq!(char_from_code(0xD800), "");
+        assert_eq!(char_from_code(0xDFFF), "");
+        assert_eq!(char_from_code(0x110000), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_char_code_string_type() {
+        let s = String::from("é");
+        assert_eq!(char_code(s), 233);
+    }
+
+    // ── is_printable / is_control / escape_control ──────────
+    #[test]
+    fn test_is_printable() {
+        assert!(is_printable("abc"));
+        assert!(is_printable("a b"));
+        assert!(is_printable("é"));
+        assert!(!is_printable("a\tb"));
+        assert!(!is_printable("\u{7}"));
+        assert!(!is_printable(""));
+    }
+
+    #[test]
+    fn test_is_control() {
+        assert!(is_control("\t"));
+        assert!(is_control("\u{7}\u{7f}"));
+        assert!(is_control("\u{85}"));
+        assert!(!is_control(" "));
+        assert!(!is_control("a\t"));
+        assert!(!is_control(""));
+    }
+
+    #[test]
+    fn test_escape_control() {
+        assert_eq!(escape_control("a\tb\u{7}c"), "a\\tb\\x07c");
+        assert_eq!(escape_control("x\r\ny"), "x\\r\\ny");
+        assert_eq!(escape_control("\u{7f}"), "\\x7f");
+        assert_eq!(escape_control("plain é"), "plain é");
+        assert_eq!(escape_control(""), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_escape_control_string_type() {
+        let s = String::from("tab\there");
+        assert!(!is_printable(s.clone()));
+        assert_eq!(escape_control(s), "tab\\there");
+    }
+
+    // ── is_ws_unicode / normalize_ws ────────────────────────
+    #[test]
+    fn test_is_ws_unicode() {
+        assert!(is_ws_unicode(" \t\n"));
+        assert!(is_ws_unicode("\u{a0}"));
+        assert!(is_ws_unicode("\u{2003}"));
+        assert!(is_ws_unicode("\u{3000}"));
+        assert!(!is_ws_unicode("a\u{a0}"));
+        assert!(!is_ws_unicode(""));
+    }
+
+    #[test]
+    fn test_is_ws_stays_ascii_only() {
+        assert!(!is_ws("\u{a0}"));
+        assert!(!is_ws("\u{2003}"));
+        assert!(!is_ws("\u{3000}"));
+    }
+
+    #[test]
+    fn test_normalize_ws() {
+        assert_eq!(normalize_ws("a\u{a0}b"), "a b");
+        assert_eq!(normalize_ws("a \u{2003}\t b"), "a b");
+        assert_eq!(normalize_ws("x\u{3000}\u{3000}y"), "x y");
+        assert_eq!(normalize_ws("\u{a0}lead"), " lead");
+        assert_eq!(normalize_ws("none"), "none");
+        assert_eq!(normalize_ws(""), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_normalize_ws_string_type() {
+        let s = String::from("node\u{a0}name");
+        assert_eq!(normalize_ws(s), "node name");
+    }
+
+    // ── is_ascii / to_ascii_lossy ───────────────────────────
+    #[test]
+    fn test_is_ascii() {
+        assert!(is_ascii("node_1"));
+        assert!(is_ascii(""));
+        assert!(!is_ascii("café"));
+    }
+
+    #[test]
+    fn test_to_ascii_lossy_accents() {
+        assert_eq!(to_ascii_lossy("café"), "cafe");
+        assert_eq!(to_ascii_lossy("naïve"), "naive");
+        assert_eq!(to_ascii_lossy("Ünïcödé"), "Unicode");
+        assert_eq!(to_ascii_lossy("Straße"), "Strasse");
+        assert_eq!(to_ascii_lossy("Ærø"), "AEro");
+    }
+
+    #[test]
+    fn test_to_ascii_lossy_combining_marks() {
+        // "e" + COMBINING ACUTE ACCENT
+        assert_eq!(to_ascii_lossy("cafe\u{301}"), "cafe");
+    }
+
+    #[test]
+    fn test_to_ascii_lossy_unmappable() {
+        assert_eq!(to_ascii_lossy("🦀"), "_");
+        assert_eq!(to_ascii_lossy("a→b"), "a_b");
+        assert_eq!(to_ascii_lossy("名"), "_");
+        assert_eq!(to_ascii_lossy(""), "");
+    }
+
+    #[test]
+    fn test_to_ascii_lossy_latin1_letters_all_ascii() {
+        for code in 0xC0..=0xFF {
+            let c = char::from_u32(code).unwrap();
+            if c == '×' || c == '÷' {
+                continue;
+            }
+            let out = to_ascii_lossy(c.to_string());
+            assert!(out.chars().all(|o| o.is_ascii_alphabetic()), "{} -> {}", c, out);
+        }
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_to_ascii_lossy_string_type() {
+        let s = String::from("Zoë");
+        assert!(!is_ascii(s.clone()));
+        assert_eq!(to_ascii_lossy(s), "Zoe");
+    }
+
+    // ── char_category ───────────────────────────────────────
+    #[test]
+    fn test_char_category_table() {
+        let table = [
+            ("a", "alpha"),
+            ("Z", "alpha"),
+            ("é", "alpha"),
+            ("名", "alpha"),
+            ("0", "digit"),
+            ("9", "digit"),
+            (" ", "ws"),
+            ("\t", "ws"),
+            ("\n", "ws"),
+            ("_", "punct"),
+            ("-", "punct"),
+            ("{", "punct"),
+            ("\u{7}", "control"),
+            ("\u{7f}", "control"),
+            ("\u{a0}", "other"),
+            ("¿", "other"),
+            ("🦀", "other"),
+            ("٣", "other"),
+            ("", "empty"),
+        ];
+        for (input, expected) in table {
+            assert_eq!(char_category(input), expected, "input {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_char_category_uses_first_char() {
+        assert_eq!(char_category("a1"), "alpha");
+        assert_eq!(char_category("1a"), "digit");
+    }
+
+    #[test]
+    fn test_char_category_agrees_with_is_functions() {
+        for code in 0..0x250 {
+            let Some(c) = char::from_u32(code) else { continue };
+            let s = c.to_string();
+            let expected = match char_category(&s).as_str() {
+                "alpha" => is_alpha(&s),
+                "digit" => is_digit(&s),
+                "ws" => is_ws(&s),
+                "punct" => is_punct(&s),
+                "control" => is_control(&s),
+                _ => !(is_alpha(&s) || is_digit(&s) || is_ws(&s) || is_punct(&s) || is_control(&s)),
+            };
+            assert!(expected, "U+{:04X}", code);
+        }
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_char_category_string_type() {
+        let s = String::from("x");
+        assert_eq!(char_category(s), "alpha");
+    }
+
+    // ── count_leading / count_class ─────────────────────────
+    #[test]
+    fn test_count_leading() {
+        assert_eq!(count_leading("    code", "ws"), 4);
+        assert_eq!(count_leading("\t\tx", "ws"), 2);
+        assert_eq!(count_leading("code", "ws"), 0);
+        assert_eq!(count_leading("123abc", "digit"), 3);
+        assert_eq!(count_leading("ab1-", "alnum"), 3);
+        assert_eq!(count_leading("", "alpha"), 0);
+    }
+
+    #[test]
+    fn test_count_class() {
+        assert_eq!(count_class("a1b2", "digit"), 2);
+        assert_eq!(count_class("a1b2", "alpha"), 2);
+        assert_eq!(count_class("a1b2", "alnum"), 4);
+        assert_eq!(count_class(" a b ", "ws"), 3);
+        assert_eq!(count_class("", "digit"), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected one of \"alpha\", \"digit\", \"ws\", \"alnum\"")]
+    fn test_count_class_unknown_name_panics() {
+        count_class("abc", "letters");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_count_leading_string_type() {
+        let line = String::from("  x");
+        let class = String::from("ws");
+        assert_eq!(count_leading(line, class), 2);
+    }
 }
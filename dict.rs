@@ -14,6 +14,51 @@
 //   // Clone a dict
 //   d2 := dict_clone(d)
 //
+//   // Every key mapped to the same default
+//   d := dict_from_keys(nodes, 0)
+//
+//   // Item -> index of its first occurrence
+//   position := dict_enumerate(ordering)
+//
+//   // Merge two maps (b wins on collision; _keep_first: a wins)
+//   d := dict_merge(a, b)
+//
+//   // Bucket (key, value) pairs: {layer: [nodes...]}
+//   layers := dict_group_by(pairs)
+//
+//   // Keep / drop entries
+//   real := dict_filter(coords, keep)   // keep(k, v) -> bool
+//   sub := dict_filter_keys(d, keys)
+//   rest := dict_without(d, keys)
+//
+//   // Transform values (f(v) -> w) or whole entries (f(k, v) -> (k2, v2))
+//   xs := dict_map_values(xs, scale)
+//   named := dict_map_entries(d, rename)
+//
+//   // Deterministic iteration (HashMap order varies run to run)
+//   entries := dict_sorted_entries(d)  // [(k, v)] by key
+//   ranked := dict_sorted_by_value(d)   // ties broken by key
+//   names := dict_keys_sorted(d)
+//   found, node, d := dict_min_by_value(dist)   // _max_, _f for floats
+//
+//   // Compare key sets (sorted output)
+//   both := dict_common_keys(a, b)
+//   only_a := dict_missing_keys(a, b)
+//   either := dict_all_keys(a, b)
+//
+//   // Insert-if-missing / counters (these take the dict by &mut)
+//   v := dict_setdefault(d, key, 0)
+//   dict_append(adj, "A", "B")         // adj["A"].push("B"), list created
+//   n := dict_increment(counts, word, 1)   // missing key counts as 0
+//
+//   // Shared handle: every clone refers to the SAME map
+//   memo := dictref_new()
+//   dictref_insert(memo, "fib_10", 55)
+//   found, v := dictref_get(memo, "fib_10")
+//   dictref_update(memo, more)          // in-place merge, more wins
+//   n := dictref_len(memo)
+//   snapshot := dictref_to_map(memo)
+//
 // Used by: Sugiyama layout (layer assignment, coordinate maps, node lookups).
 //
 // Why needed: Python uses dict comprehensions 8+ times in sugiyama.py for
@@ -24,10 +69,29 @@
 //   dict_clone takes HashMap by value (not by reference) so that .hom code
 //   like `d2 := dict_clone(d)` works correctly: homunc codegen emits
 //   `dict_clone(d.clone())` which passes an owned HashMap.
+//
+//   Dict (dictref_*) uses Rc<RefCell<HashMap>>, the same sharing model as
+//   heap.rs: the codegen's arg.clone() is an Rc clone, so a memo table or
+//   visited set passed down recursive .hom calls is mutated in place
+//   rather than copied.
+//
+// Clone depth:
+//   dict_clone             — one level: the returned map is independent of
+//                            the caller's, values are whatever the incoming
+//                            clone produced.
+//   dict_deep_clone_lists  — two levels: the owned argument already holds its
+//                            own inner Vec<String>s, so pushing to a copied
+//                            list never touches the original.
+//   dict_deep_clone_dicts  — two levels: the owned argument already holds its
+//                            own inner HashMap<String, i32>s, so inserting
+//                            into a copied inner map never touches the
+//                            original.
 // ============================================================
 
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::rc::Rc;
 
 /// Build a `HashMap` from a `Vec` of `(key, value)` pairs.
 /// If the same key appears multiple times, the last value wins.
@@ -41,6 +105,28 @@ pub fn dict_zip<K: Eq + Hash, V>(keys: Vec<K>, values: Vec<V>) -> HashMap<K, V>
     keys.into_iter().zip(values.into_iter()).collect()
 }
 
+/// Strict `dict_zip`: returns `(false, empty map)` when `keys` and `values`
+/// differ in length instead of silently truncating.
+pub fn dict_zip_strict<K: Eq + Hash, V>(keys: Vec<K>, values: Vec<V>) -> (bool, HashMap<K, V>) {
+    if keys.len() != values.len() {
+        return (false, HashMap::new());
+    }
+    (true, dict_zip(keys, values))
+}
+
+/// Strict `dict_from_pairs`: returns `(false, empty map)` when any key
+/// appears more than once instead of letting the last value win.
+pub fn dict_from_pairs_unique<K: Eq + Hash, V>(pairs: Vec<(K, V)>) -> (bool, HashMap<K, V>) {
+    let mut out = HashMap::with_capacity(pairs.len());
+    for (k, v) in pairs {
+        if out.contains_key(&k) {
+            return (false, HashMap::new());
+        }
+        out.insert(k, v);
+    }
+    (true, out)
+}
+
 /// Return a clone of `d`.
 /// Takes `d` by value so that `dict_clone(d.clone())` from .hom codegen works:
 /// the incoming clone is returned as-is (zero extra copies).
@@ -48,6 +134,288 @@ pub fn dict_clone<K: Eq + Hash + Clone, V: Clone>(d: HashMap<K, V>) -> HashMap<K
     d
 }
 
+/// Build a `HashMap` mapping every key in `keys` to a clone of `default`.
+/// Mirrors Python's `dict.fromkeys(keys, default)`; duplicate keys collapse.
+pub fn dict_from_keys<K: Eq + Hash, V: Clone>(keys: Vec<K>, default: V) -> HashMap<K, V> {
+    keys.into_iter().map(|k| (k, default.clone())).collect()
+}
+
+/// Build a `HashMap` from each item to its index in `items` (i32 for .hom int).
+/// Mirrors `{node: i for i, node in enumerate(ordering)}`, except that a
+/// repeated item keeps the index of its FIRST occurrence.
+pub fn dict_enumerate<K: Eq + Hash>(items: Vec<K>) -> HashMap<K, i32> {
+    let mut out = HashMap::new();
+    for (i, k) in items.into_iter().enumerate() {
+        out.entry(k).or_insert(i as i32);
+    }
+    out
+}
+
+/// Deep clone of a dict of string lists.
+/// Taking `d` by value is the deep-copy guarantee: codegen passes `d.clone()`,
+/// and cloning a `HashMap` clones every inner `Vec<String>`, so the owned
+/// argument is returned as-is (zero extra copies).
+pub fn dict_deep_clone_lists(d: HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+    d
+}
+
+/// Deep clone of a dict of dicts.
+/// Taking `d` by value is the deep-copy guarantee: codegen passes `d.clone()`,
+/// and cloning a `HashMap` clones every inner `HashMap<String, i32>`, so the
+/// owned argument is returned as-is (zero extra copies).
+pub fn dict_deep_clone_dicts(
+    d: HashMap<String, HashMap<String, i32>>,
+) -> HashMap<String, HashMap<String, i32>> {
+    d
+}
+
+/// Merge two maps into a new one; on a key collision the value from `b`
+/// wins, like Python's `{**a, **b}`.
+pub fn dict_merge<K: Eq + Hash, V>(a: HashMap<K, V>, b: HashMap<K, V>) -> HashMap<K, V> {
+    let mut out = a;
+    out.extend(b);
+    out
+}
+
+/// Merge two maps into a new one; on a key collision the value from `a`
+/// is kept and `b`'s is dropped.
+pub fn dict_merge_keep_first<K: Eq + Hash, V>(a: HashMap<K, V>, b: HashMap<K, V>) -> HashMap<K, V> {
+    let mut out = a;
+    for (k, v) in b {
+        out.entry(k).or_insert(v);
+    }
+    out
+}
+
+/// Group `(key, value)` pairs into a map of key -> all values for that key,
+/// values kept in input order. Replaces the contains-then-push loop for
+/// buckets like `{layer: [nodes...]}`.
+pub fn dict_group_by<K: Eq + Hash, V>(pairs: Vec<(K, V)>) -> HashMap<K, Vec<V>> {
+    let mut out: HashMap<K, Vec<V>> = HashMap::new();
+    for (k, v) in pairs {
+        out.entry(k).or_default().push(v);
+    }
+    out
+}
+
+/// Group `items` by the key `key_fn` derives from each one, items kept in
+/// input order. `key_fn` receives a clone, per the .hom calling convention.
+pub fn dict_group_by_fn<T: Clone, K: Eq + Hash>(
+    items: Vec<T>,
+    key_fn: impl Fn(T) -> K,
+) -> HashMap<K, Vec<T>> {
+    let mut out: HashMap<K, Vec<T>> = HashMap::new();
+    for item in items {
+        out.entry(key_fn(item.clone())).or_default().push(item);
+    }
+    out
+}
+
+/// Keep the entries for which `pred(key, value)` returns `true`.
+/// `pred` receives clones, per the .hom calling convention.
+pub fn dict_filter<K: Eq + Hash + Clone, V: Clone>(
+    d: HashMap<K, V>,
+    pred: impl Fn(K, V) -> bool,
+) -> HashMap<K, V> {
+    d.into_iter().filter(|(k, v)| pred(k.clone(), v.clone())).collect()
+}
+
+/// Keep only the entries whose key is listed in `keys`.
+/// Listed keys that are not in `d` are ignored.
+pub fn dict_filter_keys<K: Eq + Hash, V>(d: HashMap<K, V>, keys: Vec<K>) -> HashMap<K, V> {
+    let mut d = d;
+    keys.into_iter()
+        .filter_map(|k| d.remove_entry(&k))
+        .collect()
+}
+
+/// Drop the entries whose key is listed in `keys`.
+/// Listed keys that are not in `d` are ignored.
+pub fn dict_without<K: Eq + Hash, V>(d: HashMap<K, V>, keys: Vec<K>) -> HashMap<K, V> {
+    let mut d = d;
+    for k in keys {
+        d.remove(&k);
+    }
+    d
+}
+
+/// Apply `f` to every value, keeping keys unchanged.
+pub fn dict_map_values<K: Eq + Hash, V, W>(d: HashMap<K, V>, f: impl Fn(V) -> W) -> HashMap<K, W> {
+    d.into_iter().map(|(k, v)| (k, f(v))).collect()
+}
+
+/// Apply `f` to every `(key, value)` entry; `f` may rewrite keys as well as
+/// values. If two entries map to the same new key, the last one wins
+/// (HashMap iteration order, so avoid relying on which).
+pub fn dict_map_entries<K, V, K2: Eq + Hash, V2>(
+    d: HashMap<K, V>,
+    f: impl Fn(K, V) -> (K2, V2),
+) -> HashMap<K2, V2> {
+    d.into_iter().map(|(k, v)| f(k, v)).collect()
+}
+
+/// Return all entries sorted by key. HashMap iteration order varies from
+/// run to run; iterate through this (or `dict_keys_sorted`) whenever the
+/// output must be deterministic, e.g. golden-file tests.
+pub fn dict_sorted_entries<K: Eq + Hash + Ord, V>(d: HashMap<K, V>) -> Vec<(K, V)> {
+    let mut entries: Vec<(K, V)> = d.into_iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    entries
+}
+
+/// Return all entries sorted by value ascending, ties broken by key.
+pub fn dict_sorted_by_value<K: Eq + Hash + Ord, V: Ord>(d: HashMap<K, V>) -> Vec<(K, V)> {
+    let mut entries: Vec<(K, V)> = d.into_iter().collect();
+    entries.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    entries
+}
+
+/// Return all keys sorted ascending.
+pub fn dict_keys_sorted<K: Eq + Hash + Ord, V>(d: HashMap<K, V>) -> Vec<K> {
+    let mut keys: Vec<K> = d.into_keys().collect();
+    keys.sort();
+    keys
+}
+
+/// Python's `d.setdefault(key, default)`: insert `default` if `key` is
+/// missing, then return a clone of the value now stored at `key`.
+pub fn dict_setdefault<K: Eq + Hash, V: Clone>(d: &mut HashMap<K, V>, key: K, default: V) -> V {
+    d.entry(key).or_insert(default).clone()
+}
+
+/// Push `item` onto the list at `key`, creating an empty list first if the
+/// key is missing: `d.setdefault(key, []).append(item)` in one call.
+pub fn dict_append<K: Eq + Hash, V>(d: &mut HashMap<K, Vec<V>>, key: K, item: V) {
+    d.entry(key).or_default().push(item);
+}
+
+/// Return `(true, key, value)` for the entry with the smallest value, ties
+/// broken by the smallest key so the result is deterministic. An empty map
+/// gives `(false, K::default(), V::default())`.
+pub fn dict_min_by_value<K: Eq + Hash + Ord + Default, V: Ord + Default>(
+    d: HashMap<K, V>,
+) -> (bool, K, V) {
+    match d.into_iter().min_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0))) {
+        Some((k, v)) => (true, k, v),
+        None => (false, K::default(), V::default()),
+    }
+}
+
+/// Return `(true, key, value)` for the entry with the largest value, ties
+/// broken by the smallest key. An empty map gives
+/// `(false, K::default(), V::default())`.
+pub fn dict_max_by_value<K: Eq + Hash + Ord + Default, V: Ord + Default>(
+    d: HashMap<K, V>,
+) -> (bool, K, V) {
+    match d.into_iter().min_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))) {
+        Some((k, v)) => (true, k, v),
+        None => (false, K::default(), V::default()),
+    }
+}
+
+/// `dict_min_by_value` for f64 values (e.g. tentative distances).
+/// NaN values are skipped; a map with no non-NaN value gives
+/// `(false, K::default(), 0.0)`.
+pub fn dict_min_by_value_f<K: Eq + Hash + Ord + Default>(d: HashMap<K, f64>) -> (bool, K, f64) {
+    match d
+        .into_iter()
+        .filter(|(_, v)| !v.is_nan())
+        .min_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)))
+    {
+        Some((k, v)) => (true, k, v),
+        None => (false, K::default(), 0.0),
+    }
+}
+
+/// `dict_max_by_value` for f64 values. NaN values are skipped; a map with
+/// no non-NaN value gives `(false, K::default(), 0.0)`.
+pub fn dict_max_by_value_f<K: Eq + Hash + Ord + Default>(d: HashMap<K, f64>) -> (bool, K, f64) {
+    match d
+        .into_iter()
+        .filter(|(_, v)| !v.is_nan())
+        .min_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)))
+    {
+        Some((k, v)) => (true, k, v),
+        None => (false, K::default(), 0.0),
+    }
+}
+
+/// Keys present in both `a` and `b`, sorted. Values are never cloned.
+pub fn dict_common_keys<K: Eq + Hash + Ord, V, W>(a: HashMap<K, V>, b: HashMap<K, W>) -> Vec<K> {
+    let mut keys: Vec<K> = a.into_keys().filter(|k| b.contains_key(k)).collect();
+    keys.sort();
+    keys
+}
+
+/// Keys present in `a` but not in `b`, sorted. Values are never cloned.
+pub fn dict_missing_keys<K: Eq + Hash + Ord, V, W>(a: HashMap<K, V>, b: HashMap<K, W>) -> Vec<K> {
+    let mut keys: Vec<K> = a.into_keys().filter(|k| !b.contains_key(k)).collect();
+    keys.sort();
+    keys
+}
+
+/// Keys present in either `a` or `b`, sorted and without duplicates.
+pub fn dict_all_keys<K: Eq + Hash + Ord, V, W>(a: HashMap<K, V>, b: HashMap<K, W>) -> Vec<K> {
+    let mut keys: Vec<K> = b.into_keys().filter(|k| !a.contains_key(k)).collect();
+    keys.extend(a.into_keys());
+    keys.sort();
+    keys
+}
+
+/// Add `delta` to the count at `key` (a missing key counts as 0), store the
+/// result and return it. Negative deltas may go below zero; the key is
+/// never removed.
+pub fn dict_increment<K: Eq + Hash>(d: &mut HashMap<K, i32>, key: K, delta: i32) -> i32 {
+    let v = d.entry(key).or_insert(0);
+    *v += delta;
+    *v
+}
+
+/// `dict_increment` for f64 accumulators (e.g. summed edge weights).
+pub fn dict_increment_f<K: Eq + Hash>(d: &mut HashMap<K, f64>, key: K, delta: f64) -> f64 {
+    let v = d.entry(key).or_insert(0.0);
+    *v += delta;
+    *v
+}
+
+/// Map shared through Rc<RefCell<...>>: clones of a Dict handle all refer
+/// to one HashMap, so mutation through any handle is visible through all.
+pub type Dict<K, V> = Rc<RefCell<HashMap<K, V>>>;
+
+/// Create a new empty shared dict.
+pub fn dictref_new<K: Eq + Hash, V>() -> Dict<K, V> {
+    Rc::new(RefCell::new(HashMap::new()))
+}
+
+/// Insert or overwrite `key` with `value`.
+/// Accepts impl Into<K> so &str literals work for String-keyed dicts.
+pub fn dictref_insert<K: Eq + Hash, V>(d: Dict<K, V>, key: impl Into<K>, value: V) {
+    d.borrow_mut().insert(key.into(), value);
+}
+
+/// Return `(true, value)` for a present key, `(false, V::default())` otherwise.
+pub fn dictref_get<K: Eq + Hash, V: Clone + Default>(d: Dict<K, V>, key: impl Into<K>) -> (bool, V) {
+    match d.borrow().get(&key.into()) {
+        Some(v) => (true, v.clone()),
+        None => (false, V::default()),
+    }
+}
+
+/// Merge `src` into the shared dict in place; `src` wins on key collision.
+pub fn dictref_update<K: Eq + Hash, V>(dst: Dict<K, V>, src: HashMap<K, V>) {
+    dst.borrow_mut().extend(src);
+}
+
+/// Return an independent copy of the current contents as a plain HashMap.
+pub fn dictref_to_map<K: Eq + Hash + Clone, V: Clone>(d: Dict<K, V>) -> HashMap<K, V> {
+    d.borrow().clone()
+}
+
+/// Return the number of entries (i32 for .hom int compatibility).
+pub fn dictref_len<K, V>(d: Dict<K, V>) -> i32 {
+    d.borrow().len() as i32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,6 +522,53 @@ mod tests {
         assert_eq!(d["only"], 42);
     }
 
+    // ── dict_zip_strict ─────────────────────────────────────
+
+    #[test]
+    fn test_zip_strict_equal_lengths() {
+        let (ok, d) = dict_zip_strict(vec!["a".to_string(), "b".to_string()], vec![1, 2]);
+        assert!(ok);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d["b"], 2);
+    }
+
+    #[test]
+    fn test_zip_strict_empty_is_ok() {
+        let (ok, d): (bool, HashMap<String, i32>) = dict_zip_strict(vec![], vec![]);
+        assert!(ok);
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_zip_strict_length_mismatch() {
+        let (ok, d) = dict_zip_strict(vec!["a".to_string(), "b".to_string()], vec![1]);
+        assert!(!ok);
+        assert!(d.is_empty());
+        let (ok, _) = dict_zip_strict(vec!["a".to_string()], vec![1, 2]);
+        assert!(!ok);
+    }
+
+    // ── dict_from_pairs_unique ──────────────────────────────
+
+    #[test]
+    fn test_from_pairs_unique_no_duplicates() {
+        let (ok, d) = dict_from_pairs_unique(vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+        assert!(ok);
+        assert_eq!(d["a"], 1);
+        assert_eq!(d["b"], 2);
+    }
+
+    #[test]
+    fn test_from_pairs_unique_duplicate_reports_false() {
+        let (ok, d) = dict_from_pairs_unique(vec![
+            ("x".to_string(), 10),
+            ("y".to_string(), 5),
+            ("x".to_string(), 20),
+        ]);
+        assert!(!ok);
+        assert!(d.is_empty());
+    }
+
     // ── dict_clone ──────────────────────────────────────────
 
     #[test]
@@ -194,4 +609,528 @@ mod tests {
         original.insert("k".to_string(), 2);
         assert_eq!(cloned["k"], 1);
     }
+
+    // ── dict_from_keys ──────────────────────────────────────
+
+    #[test]
+    fn test_from_keys_empty() {
+        let d: HashMap<String, i32> = dict_from_keys(vec![], 0);
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_from_keys_same_default() {
+        let d = dict_from_keys(vec!["a".to_string(), "b".to_string()], 7);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d["a"], 7);
+        assert_eq!(d["b"], 7);
+    }
+
+    #[test]
+    fn test_from_keys_visited_bool() {
+        let nodes = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        let visited = dict_from_keys(nodes, false);
+        assert_eq!(visited.len(), 3);
+        assert!(visited.values().all(|v| !*v));
+        assert!(!visited["B"]);
+    }
+
+    #[test]
+    fn test_from_keys_duplicate_keys_collapse() {
+        let d = dict_from_keys(vec!["a".to_string(), "a".to_string()], 0);
+        assert_eq!(d.len(), 1);
+    }
+
+    // ── dict_enumerate ──────────────────────────────────────
+
+    #[test]
+    fn test_enumerate_empty() {
+        let d: HashMap<String, i32> = dict_enumerate(vec![]);
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_enumerate_sugiyama_pattern() {
+        let ordering = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+        let position = dict_enumerate(ordering);
+        assert_eq!(position["A"], 0);
+        assert_eq!(position["B"], 1);
+        assert_eq!(position["C"], 2);
+    }
+
+    #[test]
+    fn test_enumerate_duplicate_first_wins() {
+        let d = dict_enumerate(vec!["x".to_string(), "y".to_string(), "x".to_string()]);
+        assert_eq!(d.len(), 2);
+        assert_eq!(d["x"], 0);
+        assert_eq!(d["y"], 1);
+    }
+
+    // ── dict_deep_clone_lists ───────────────────────────────
+
+    #[test]
+    fn test_deep_clone_lists_inner_push_independent() {
+        let mut original: HashMap<String, Vec<String>> = HashMap::new();
+        original.insert("A".to_string(), vec!["B".to_string()]);
+        let mut copy = dict_deep_clone_lists(original.clone());
+        copy.get_mut("A").unwrap().push("C".to_string());
+        assert_eq!(original["A"], vec!["B".to_string()]);
+        assert_eq!(copy["A"], vec!["B".to_string(), "C".to_string()]);
+    }
+
+    #[test]
+    fn test_deep_clone_lists_original_mutation_independent() {
+        let mut original: HashMap<String, Vec<String>> = HashMap::new();
+        original.insert("A".to_string(), vec![]);
+        let copy = dict_deep_clone_lists(original.clone());
+        original.get_mut("A").unwrap().push("X".to_string());
+        assert!(copy["A"].is_empty());
+    }
+
+    #[test]
+    fn test_deep_clone_lists_empty() {
+        assert!(dict_deep_clone_lists(HashMap::new()).is_empty());
+    }
+
+    // ── dict_deep_clone_dicts ───────────────────────────────
+
+    #[test]
+    fn test_deep_clone_dicts_inner_insert_independent() {
+        let mut inner = HashMap::new();
+        inner.insert("x".to_string(), 1);
+        let mut original = HashMap::new();
+        original.insert("layer".to_string(), inner);
+        let mut copy = dict_deep_clone_dicts(original.clone());
+        copy.get_mut("layer").unwrap().insert("x".to_string(), 99);
+        copy.get_mut("layer").unwrap().insert("y".to_string(), 2);
+        assert_eq!(original["layer"].len(), 1);
+        assert_eq!(original["layer"]["x"], 1);
+        assert_eq!(copy["layer"]["x"], 99);
+    }
+
+    #[test]
+    fn test_deep_clone_dicts_preserves_entries() {
+        let mut inner = HashMap::new();
+        inner.insert("a".to_string(), 1);
+        inner.insert("b".to_string(), 2);
+        let mut original = HashMap::new();
+        original.insert("k".to_string(), inner);
+        let copy = dict_deep_clone_dicts(original.clone());
+        assert_eq!(copy, original);
+    }
+
+    // ── dict_merge / dict_merge_keep_first ──────────────────
+
+    #[test]
+    fn test_merge_disjoint() {
+        let a = dict_from_pairs(vec![("A".to_string(), 0)]);
+        let b = dict_from_pairs(vec![("B".to_string(), 1)]);
+        let d = dict_merge(a.clone(), b.clone());
+        assert_eq!(d.len(), 2);
+        assert_eq!(d["A"], 0);
+        assert_eq!(d["B"], 1);
+        assert_eq!(dict_merge_keep_first(a, b), d);
+    }
+
+    #[test]
+    fn test_merge_collision_second_wins() {
+        let a = dict_from_pairs(vec![("n".to_string(), 1), ("a".to_string(), 5)]);
+        let b = dict_from_pairs(vec![("n".to_string(), 2)]);
+        let d = dict_merge(a, b);
+        assert_eq!(d["n"], 2);
+        assert_eq!(d["a"], 5);
+    }
+
+    #[test]
+    fn test_merge_keep_first_collision_first_wins() {
+        let a = dict_from_pairs(vec![("n".to_string(), 1)]);
+        let b = dict_from_pairs(vec![("n".to_string(), 2), ("b".to_string(), 6)]);
+        let d = dict_merge_keep_first(a, b);
+        assert_eq!(d["n"], 1);
+        assert_eq!(d["b"], 6);
+    }
+
+    #[test]
+    fn test_merge_inputs_unchanged() {
+        let a = dict_from_pairs(vec![("k".to_string(), 1)]);
+        let b = dict_from_pairs(vec![("k".to_string(), 2)]);
+        let _ = dict_merge(a.clone(), b.clone());
+        assert_eq!(a["k"], 1);
+        assert_eq!(b["k"], 2);
+    }
+
+    // ── dict_group_by / dict_group_by_fn ────────────────────
+
+    #[test]
+    fn test_group_by_empty() {
+        let d: HashMap<i32, Vec<String>> = dict_group_by(vec![]);
+        assert!(d.is_empty());
+        let d: HashMap<usize, Vec<String>> = dict_group_by_fn(vec![], |s: String| s.len());
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_group_by_layers_keep_input_order() {
+        let pairs = vec![
+            (0, "A".to_string()),
+            (1, "C".to_string()),
+            (0, "B".to_string()),
+            (1, "D".to_string()),
+            (2, "E".to_string()),
+        ];
+        let layers = dict_group_by(pairs);
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers[&0], vec!["A".to_string(), "B".to_string()]);
+        assert_eq!(layers[&1], vec!["C".to_string(), "D".to_string()]);
+        assert_eq!(layers[&2], vec!["E".to_string()]);
+    }
+
+    #[test]
+    fn test_group_by_fn_first_letter() {
+        let words = vec![
+            "apple".to_string(),
+            "bean".to_string(),
+            "avocado".to_string(),
+            "beet".to_string(),
+        ];
+        let d = dict_group_by_fn(words, |w: String| w[..1].to_string());
+        assert_eq!(d["a"], vec!["apple".to_string(), "avocado".to_string()]);
+        assert_eq!(d["b"], vec!["bean".to_string(), "beet".to_string()]);
+    }
+
+    // ── dict_filter / dict_filter_keys / dict_without ───────
+
+    #[test]
+    fn test_filter_drops_dummy_nodes() {
+        let coords = dict_from_pairs(vec![
+            ("A".to_string(), 0),
+            ("_dummy1".to_string(), 1),
+            ("B".to_string(), 2),
+        ]);
+        let real = dict_filter(coords.clone(), |k: String, _v: i32| !k.starts_with("_dummy"));
+        assert_eq!(real.len(), 2);
+        assert!(real.contains_key("A"));
+        assert!(real.contains_key("B"));
+        assert_eq!(coords.len(), 3);
+    }
+
+    #[test]
+    fn test_filter_by_value() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), 5)]);
+        let big = dict_filter(d, |_k: String, v: i32| v > 2);
+        assert_eq!(big.len(), 1);
+        assert_eq!(big["b"], 5);
+    }
+
+    #[test]
+    fn test_filter_keys_ignores_missing() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+        let kept = dict_filter_keys(d.clone(), vec!["b".to_string(), "zzz".to_string()]);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept["b"], 2);
+        assert_eq!(d.len(), 2);
+    }
+
+    #[test]
+    fn test_without_ignores_missing() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+        let rest = dict_without(d.clone(), vec!["a".to_string(), "zzz".to_string()]);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest["b"], 2);
+        assert_eq!(d["a"], 1);
+    }
+
+    // ── dict_map_values / dict_map_entries ──────────────────
+
+    #[test]
+    fn test_map_values_double() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), -4)]);
+        let doubled = dict_map_values(d, |v: i32| v * 2);
+        assert_eq!(doubled["a"], 2);
+        assert_eq!(doubled["b"], -8);
+    }
+
+    #[test]
+    fn test_map_values_change_type() {
+        let d = dict_from_pairs(vec![("x".to_string(), 3)]);
+        let s = dict_map_values(d, |v: i32| v.to_string());
+        assert_eq!(s["x"], "3");
+    }
+
+    #[test]
+    fn test_map_entries_prefix_keys() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+        let renamed = dict_map_entries(d, |k: String, v: i32| (format!("node_{}", k), v));
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed["node_a"], 1);
+        assert_eq!(renamed["node_b"], 2);
+    }
+
+    #[test]
+    fn test_map_entries_key_collision_collapses() {
+        let d = dict_from_pairs(vec![("a".to_string(), 1), ("b".to_string(), 1)]);
+        let merged = dict_map_entries(d, |_k: String, v: i32| ("same".to_string(), v));
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged["same"], 1);
+    }
+
+    #[test]
+    fn test_map_values_empty() {
+        let d: HashMap<String, i32> = HashMap::new();
+        assert!(dict_map_values(d, |v: i32| v + 1).is_empty());
+    }
+
+    // ── Dict shared handle (dictref_*) ──────────────────────
+
+    #[test]
+    fn test_dictref_new_empty() {
+        let d: Dict<String, i32> = dictref_new();
+        assert_eq!(dictref_len(d.clone()), 0);
+        assert_eq!(dictref_get(d.clone(), "x"), (false, 0));
+    }
+
+    #[test]
+    fn test_dictref_mutation_visible_through_clone() {
+        let d1: Dict<String, i32> = dictref_new();
+        let d2 = d1.clone();
+        dictref_insert(d1.clone(), "a", 1);
+        assert_eq!(dictref_get(d2.clone(), "a"), (true, 1));
+        dictref_insert(d2.clone(), "a", 7);
+        assert_eq!(dictref_get(d1.clone(), "a"), (true, 7));
+        assert_eq!(dictref_len(d1.clone()), 1);
+    }
+
+    #[test]
+    fn test_dictref_shared_memo_across_recursion() {
+        fn fib(n: i32, memo: Dict<i32, i64>) -> i64 {
+            if n < 2 {
+                return n as i64;
+            }
+            let (found, v) = dictref_get(memo.clone(), n);
+            if found {
+                return v;
+            }
+            let v = fib(n - 1, memo.clone()) + fib(n - 2, memo.clone());
+            dictref_insert(memo.clone(), n, v);
+            v
+        }
+        let memo = dictref_new();
+        assert_eq!(fib(50, memo.clone()), 12586269025);
+        assert_eq!(dictref_len(memo.clone()), 49);
+    }
+
+    #[test]
+    fn test_dictref_update_src_wins() {
+        let d: Dict<String, i32> = dictref_new();
+        dictref_insert(d.clone(), "a", 1);
+        dictref_insert(d.clone(), "b", 2);
+        let src = dict_from_pairs(vec![("b".to_string(), 20), ("c".to_string(), 30)]);
+        dictref_update(d.clone(), src);
+        let m = dictref_to_map(d.clone());
+        assert_eq!(m.len(), 3);
+        assert_eq!(m["a"], 1);
+        assert_eq!(m["b"], 20);
+        assert_eq!(m["c"], 30);
+    }
+
+    #[test]
+    fn test_dictref_to_map_is_independent() {
+        let d: Dict<String, i32> = dictref_new();
+        dictref_insert(d.clone(), "a", 1);
+        let mut m = dictref_to_map(d.clone());
+        m.insert("b".to_string(), 2);
+        assert_eq!(dictref_len(d.clone()), 1);
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_dictref_string_keys() {
+        let d: Dict<String, bool> = dictref_new();
+        let key = String::from("node");
+        dictref_insert(d.clone(), key.clone(), true);
+        assert_eq!(dictref_get(d.clone(), key), (true, true));
+    }
+
+    // ── dict_sorted_entries / dict_sorted_by_value / dict_keys_sorted
+
+    #[test]
+    fn test_sorted_entries_independent_of_insertion_order() {
+        let pairs = vec![
+            ("c".to_string(), 3),
+            ("a".to_string(), 1),
+            ("d".to_string(), 0),
+            ("b".to_string(), 2),
+        ];
+        let mut reversed = pairs.clone();
+        reversed.reverse();
+        let x = dict_sorted_entries(dict_from_pairs(pairs));
+        let y = dict_sorted_entries(dict_from_pairs(reversed));
+        assert_eq!(x, y);
+        let keys: Vec<&str> = x.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_sorted_by_value_key_tiebreak() {
+        let d = dict_from_pairs(vec![
+            ("z".to_string(), 1),
+            ("m".to_string(), 0),
+            ("a".to_string(), 1),
+        ]);
+        assert_eq!(
+            dict_sorted_by_value(d),
+            vec![("m".to_string(), 0), ("a".to_string(), 1), ("z".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_keys_sorted() {
+        let d = dict_from_pairs(vec![("b".to_string(), 0), ("a".to_string(), 0)]);
+        assert_eq!(dict_keys_sorted(d), vec!["a".to_string(), "b".to_string()]);
+        let empty: HashMap<String, i32> = HashMap::new();
+        assert!(dict_keys_sorted(empty).is_empty());
+    }
+
+    // ── dict_setdefault / dict_append ───────────────────────
+
+    #[test]
+    fn test_setdefault_inserts_when_missing() {
+        let mut d: HashMap<String, i32> = HashMap::new();
+        assert_eq!(dict_setdefault(&mut d, "a".to_string(), 5), 5);
+        assert_eq!(d["a"], 5);
+    }
+
+    #[test]
+    fn test_setdefault_keeps_existing() {
+        let mut d = dict_from_pairs(vec![("a".to_string(), 1)]);
+        assert_eq!(dict_setdefault(&mut d, "a".to_string(), 5), 1);
+        assert_eq!(d["a"], 1);
+        assert_eq!(d.len(), 1);
+    }
+
+    #[test]
+    fn test_append_builds_adjacency() {
+        let mut adj: HashMap<String, Vec<String>> = HashMap::new();
+        dict_append(&mut adj, "A".to_string(), "B".to_string());
+        dict_append(&mut adj, "A".to_string(), "C".to_string());
+        dict_append(&mut adj, "B".to_string(), "C".to_string());
+        dict_append(&mut adj, "A".to_string(), "D".to_string());
+        assert_eq!(adj.len(), 2);
+        assert_eq!(adj["A"], vec!["B".to_string(), "C".to_string(), "D".to_string()]);
+        assert_eq!(adj["B"], vec!["C".to_string()]);
+    }
+
+    // ── dict_min_by_value / dict_max_by_value (+ _f) ────────
+
+    #[test]
+    fn test_min_max_by_value_empty() {
+        let d: HashMap<String, i32> = HashMap::new();
+        assert_eq!(dict_min_by_value(d.clone()), (false, String::new(), 0));
+        assert_eq!(dict_max_by_value(d), (false, String::new(), 0));
+        let f: HashMap<String, f64> = HashMap::new();
+        assert_eq!(dict_min_by_value_f(f.clone()), (false, String::new(), 0.0));
+        assert_eq!(dict_max_by_value_f(f), (false, String::new(), 0.0));
+    }
+
+    #[test]
+    fn test_min_max_by_value_single() {
+        let d = dict_from_pairs(vec![("a".to_string(), 4)]);
+        assert_eq!(dict_min_by_value(d.clone()), (true, "a".to_string(), 4));
+        assert_eq!(dict_max_by_value(d), (true, "a".to_string(), 4));
+    }
+
+    #[test]
+    fn test_min_max_by_value_ties_smallest_key() {
+        let d = dict_from_pairs(vec![
+            ("c".to_string(), 1),
+            ("b".to_string(), 1),
+            ("x".to_string(), 9),
+            ("y".to_string(), 9),
+        ]);
+        assert_eq!(dict_min_by_value(d.clone()), (true, "b".to_string(), 1));
+        assert_eq!(dict_max_by_value(d), (true, "x".to_string(), 9));
+    }
+
+    #[test]
+    fn test_min_max_by_value_f_distances() {
+        let dist = dict_from_pairs(vec![
+            ("A".to_string(), 2.5),
+            ("B".to_string(), 0.5),
+            ("C".to_string(), f64::NAN),
+            ("D".to_string(), 0.5),
+            ("E".to_string(), 7.0),
+        ]);
+        assert_eq!(dict_min_by_value_f(dist.clone()), (true, "B".to_string(), 0.5));
+        assert_eq!(dict_max_by_value_f(dist), (true, "E".to_string(), 7.0));
+    }
+
+    // ── dict_common_keys / dict_missing_keys / dict_all_keys ─
+
+    fn keys_of(names: &[&str]) -> HashMap<String, i32> {
+        names.iter().map(|n| (n.to_string(), 0)).collect()
+    }
+
+    fn strings(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_key_sets_disjoint() {
+        let a = keys_of(&["a", "b"]);
+        let b: HashMap<String, f64> = [("c".to_string(), 1.0)].into_iter().collect();
+        assert!(dict_common_keys(a.clone(), b.clone()).is_empty());
+        assert_eq!(dict_missing_keys(a.clone(), b.clone()), strings(&["a", "b"]));
+        assert_eq!(dict_all_keys(a, b), strings(&["a", "b", "c"]));
+    }
+
+    #[test]
+    fn test_key_sets_overlapping() {
+        let a = keys_of(&["d", "a", "b"]);
+        let b = keys_of(&["c", "b", "d"]);
+        assert_eq!(dict_common_keys(a.clone(), b.clone()), strings(&["b", "d"]));
+        assert_eq!(dict_missing_keys(a.clone(), b.clone()), strings(&["a"]));
+        assert_eq!(dict_missing_keys(b.clone(), a.clone()), strings(&["c"]));
+        assert_eq!(dict_all_keys(a, b), strings(&["a", "b", "c", "d"]));
+    }
+
+    #[test]
+    fn test_key_sets_identical() {
+        let a = keys_of(&["x", "y"]);
+        assert_eq!(dict_common_keys(a.clone(), a.clone()), strings(&["x", "y"]));
+        assert!(dict_missing_keys(a.clone(), a.clone()).is_empty());
+        assert_eq!(dict_all_keys(a.clone(), a), strings(&["x", "y"]));
+    }
+
+    // ── dict_increment / dict_increment_f ───────────────────
+
+    #[test]
+    fn test_increment_word_frequency() {
+        let words = strings(&["a", "b", "a", "c", "a", "b"]);
+        let mut freq: HashMap<String, i32> = HashMap::new();
+        for w in words {
+            dict_increment(&mut freq, w, 1);
+        }
+        assert_eq!(freq.len(), 3);
+        assert_eq!(freq["a"], 3);
+        assert_eq!(freq["b"], 2);
+        assert_eq!(freq["c"], 1);
+    }
+
+    #[test]
+    fn test_increment_returns_new_value_and_goes_negative() {
+        let mut d: HashMap<String, i32> = HashMap::new();
+        assert_eq!(dict_increment(&mut d, "k".to_string(), 2), 2);
+        assert_eq!(dict_increment(&mut d, "k".to_string(), -5), -3);
+        assert_eq!(dict_increment(&mut d, "k".to_string(), 3), 0);
+        assert!(d.contains_key("k"));
+        assert_eq!(dict_increment(&mut d, "new".to_string(), -1), -1);
+    }
+
+    #[test]
+    fn test_increment_f_edge_weights() {
+        let mut w: HashMap<(i32, i32), f64> = HashMap::new();
+        dict_increment_f(&mut w, (0, 1), 0.5);
+        assert_eq!(dict_increment_f(&mut w, (0, 1), 1.25), 1.75);
+        assert_eq!(dict_increment_f(&mut w, (1, 2), -0.5), -0.5);
+    }
 }
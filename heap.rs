@@ -1,5 +1,5 @@
 // ============================================================
-// Homun Runtime — heap.rs: Priority Queue (min-heap)
+// Homun Runtime — heap.rs: Priority Queue (min-heap or max-heap)
 // Part B1 — stdlib, no external crates required.
 //
 // Usage in .hom:
@@ -9,63 +9,271 @@
 //   heap_push(h, 5, "node_a")    // priority, item
 //   heap_push(h, 2, "node_b")
 //   heap_pop(h)                   // discards lowest-priority item
+//   heap_peek(h)                  // lowest-priority item, not removed
+//   best := heap_pop_many(h, 3)   // up to 3 lowest, in order
+//   heap_contains(h, "node_a")    // any priority; O(n)
+//   heap_remove(h, "node_a")      // false if absent; O(n) rebuild
+//   heap_update_priority(h, "node_b", 1)  // decrease-key; O(n)
 //   n := heap_len(h)
 //   empty := heap_is_empty(h)
+//   heap_reserve(h, 64)           // pre-allocate
+//   heap_clear(h)                 // empty it, keeping capacity
+//   all := heap_drain_sorted(h)   // [(priority, item)] in pop order; empties h
+//   snap := heap_to_sorted_vec(h) // same, but h is left intact
+//   items := heap_items(h)        // unordered snapshot, not drained
+//   branch := heap_clone_deep(h)  // independent copy for what-if search
+//
+//   mh := heap_new_max()          // same API, largest priority first
+//
+//   h2 := heap_from_pairs(pairs)  // [(priority, item)], heapified at once
+//   heap_push_all(h2, more)       // extend with another pair list
+//
+// Generic items (Rust side):
+//   heap_new_of::<T>() / heap_new_max_of::<T>() build a Heap<T> for any
+//   T: Ord, e.g. (i32, i32) grid cells, avoiding string round trips.
+//   Heap with no parameter is Heap<String>, so heap_new() and all
+//   existing String call sites are unchanged.
 //
 // Implementation note:
-//   Uses Rc<RefCell<BinaryHeap<...>>> so that Homun's clone-based
+//   Uses Rc<RefCell<HeapInner>> so that Homun's clone-based
 //   calling convention (every variable argument becomes arg.clone())
 //   still refers to the SAME underlying heap. Rc::clone() is a
 //   cheap reference-count increment, not a deep copy, so all
 //   "copies" of a Heap value share one BinaryHeap.
+//   Use heap_clone_deep when a separate heap is actually wanted.
 //
-//   BinaryHeap wrapped with Reverse<i32> gives min-heap semantics
-//   (smallest priority value is popped first).
+//   Each heap stores its own ordering flag. Entries are keyed by an
+//   i64: -priority for a min-heap, +priority for a max-heap, so the
+//   std BinaryHeap (a max-heap) pops the right end in both cases.
+//   Widening to i64 means i32::MIN and i32::MAX never overflow when
+//   negated.
 //
 //   priority and return types use i32 to match .hom's int type.
-//   item accepts impl AsRef<str> to work with both &str literals
-//   (Rust tests) and String values (homunc codegen emits .to_string()
-//   on all string literals when passing as function arguments).
+//   item accepts impl Into<T>; for Heap<String> that covers &str
+//   literals (Rust tests), String values (homunc codegen emits
+//   .to_string() on all string literals when passing as function
+//   arguments) and &String.
 // ============================================================
 
 use std::cell::RefCell;
-use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::rc::Rc;
 
-/// Priority queue: min-heap keyed by i32, storing String items.
-/// Rc<RefCell<...>> allows .hom's clone-based calling convention to
-/// mutate through all handles that refer to the same heap.
-pub type Heap = Rc<RefCell<BinaryHeap<(Reverse<i32>, String)>>>;
+/// Heap storage plus the ordering flag chosen at construction.
+pub struct HeapInner<T> {
+    pub data: BinaryHeap<(i64, T)>,
+    pub max: bool,
+}
+
+impl<T: Ord> HeapInner<T> {
+    fn new(max: bool) -> Self {
+        HeapInner { data: BinaryHeap::new(), max }
+    }
+
+    /// Map a priority to the key stored in the (max-)BinaryHeap.
+    fn key(&self, priority: i32) -> i64 {
+        if self.max { priority as i64 } else { -(priority as i64) }
+    }
+
+    /// Inverse of `key`.
+    fn priority(&self, key: i64) -> i32 {
+        (if self.max { key } else { -key }) as i32
+    }
+}
+
+/// Priority queue keyed by i32, storing `T` items (String by default);
+/// min-heap or max-heap depending on the constructor. Rc<RefCell<...>>
+/// allows .hom's clone-based calling convention to mutate through all
+/// handles that refer to the same heap.
+pub type Heap<T = String> = Rc<RefCell<HeapInner<T>>>;
 
-/// Create a new empty min-heap.
+/// Create a new empty min-heap of String items (smallest priority popped first).
 pub fn heap_new() -> Heap {
-    Rc::new(RefCell::new(BinaryHeap::new()))
+    heap_new_of()
+}
+
+/// Create a new empty max-heap of String items (largest priority popped first).
+/// Every other heap_* function works on it unchanged.
+pub fn heap_new_max() -> Heap {
+    heap_new_max_of()
+}
+
+/// Create a new empty min-heap of `T` items.
+pub fn heap_new_of<T: Ord>() -> Heap<T> {
+    Rc::new(RefCell::new(HeapInner::new(false)))
+}
+
+/// Create a new empty max-heap of `T` items.
+pub fn heap_new_max_of<T: Ord>() -> Heap<T> {
+    Rc::new(RefCell::new(HeapInner::new(true)))
 }
 
 /// Push `item` onto the heap with the given `priority`.
-/// Items with lower priority values are popped first (min-heap).
-/// Accepts impl AsRef<str> so that &str literals and String values
-/// (emitted by homunc for .hom string args) both work.
-pub fn heap_push(h: Heap, priority: i32, item: impl AsRef<str>) {
-    h.borrow_mut()
-        .push((Reverse(priority), item.as_ref().to_string()));
+/// Items with lower priority values are popped first (min-heap), or higher
+/// values first for a heap created with `heap_new_max`.
+/// Accepts impl Into<T> so that, for a String heap, &str literals and
+/// String values (emitted by homunc for .hom string args) both work.
+pub fn heap_push<T: Ord>(h: Heap<T>, priority: i32, item: impl Into<T>) {
+    let mut inner = h.borrow_mut();
+    let key = inner.key(priority);
+    inner.data.push((key, item.into()));
+}
+
+/// Build a min-heap from `(priority, item)` pairs in one shot.
+/// Uses BinaryHeap::from (linear-time heapify) instead of n pushes; pop
+/// order is the same as pushing the pairs one by one.
+pub fn heap_from_pairs<T: Ord>(pairs: Vec<(i32, T)>) -> Heap<T> {
+    let mut inner = HeapInner::new(false);
+    let keyed = pairs.into_iter().map(|(p, item)| (inner.key(p), item)).collect::<Vec<_>>();
+    inner.data = BinaryHeap::from(keyed);
+    Rc::new(RefCell::new(inner))
+}
+
+/// Push every `(priority, item)` pair onto an existing heap, honouring its
+/// min/max ordering. Borrows the RefCell once for the whole batch.
+pub fn heap_push_all<T: Ord>(h: Heap<T>, pairs: Vec<(i32, T)>) {
+    let mut inner = h.borrow_mut();
+    let keyed = pairs.into_iter().map(|(p, item)| (inner.key(p), item)).collect::<Vec<_>>();
+    inner.data.extend(keyed);
+}
+
+/// Return `true` if any entry's item equals `item`, regardless of priority.
+/// O(n): BinaryHeap has no index by item.
+pub fn heap_contains<T: Ord>(h: Heap<T>, item: impl Into<T>) -> bool {
+    let item = item.into();
+    h.borrow().data.iter().any(|(_, s)| *s == item)
+}
+
+/// Remove the first entry whose item equals `item` (regardless of priority)
+/// and rebuild the heap. Returns `false`, leaving the heap untouched, if no
+/// entry matches. O(n): the backing Vec is scanned and re-heapified.
+pub fn heap_remove<T: Ord>(h: Heap<T>, item: impl Into<T>) -> bool {
+    let item = item.into();
+    let mut inner = h.borrow_mut();
+    // iter() walks the backing Vec, so the index is valid after into_vec().
+    let Some(i) = inner.data.iter().position(|(_, s)| *s == item) else {
+        return false;
+    };
+    let mut entries = std::mem::take(&mut inner.data).into_vec();
+    entries.swap_remove(i);
+    inner.data = BinaryHeap::from(entries);
+    true
+}
+
+/// Change the priority of the entry whose item equals `item` and restore
+/// heap order (decrease-key for Dijkstra, but raising works too). If the item
+/// is queued more than once, the copy with the lowest current priority is
+/// updated. Returns `false`, leaving the heap untouched, if no entry matches.
+/// O(n): the backing Vec is scanned and re-heapified.
+pub fn heap_update_priority<T: Ord>(h: Heap<T>, item: impl Into<T>, new_priority: i32) -> bool {
+    let item = item.into();
+    let mut inner = h.borrow_mut();
+    let Some(i) = inner
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, s))| *s == item)
+        .min_by_key(|(_, (key, _))| inner.priority(*key))
+        .map(|(i, _)| i)
+    else {
+        return false;
+    };
+    let key = inner.key(new_priority);
+    let mut entries = std::mem::take(&mut inner.data).into_vec();
+    entries[i].0 = key;
+    inner.data = BinaryHeap::from(entries);
+    true
+}
+
+/// Remove all entries, keeping the allocated capacity so the heap can be
+/// reused (e.g. one frontier across several searches). Visible through
+/// every handle, like any other mutation.
+pub fn heap_clear<T>(h: Heap<T>) {
+    h.borrow_mut().data.clear();
+}
+
+/// Pre-allocate space for at least `n` more entries. `n <= 0` is a no-op.
+pub fn heap_reserve<T>(h: Heap<T>, n: i32) {
+    if n > 0 {
+        h.borrow_mut().data.reserve(n as usize);
+    }
+}
+
+/// Pop and return the next `(priority, item)` pair: lowest priority for a
+/// min-heap, highest for a max-heap. Returns `None` if the heap is empty.
+pub fn heap_pop<T: Ord>(h: Heap<T>) -> Option<(i32, T)> {
+    let mut inner = h.borrow_mut();
+    let (key, s) = inner.data.pop()?;
+    Some((inner.priority(key), s))
+}
+
+/// Pop up to `k` entries in pop order (ascending priority for a min-heap)
+/// under a single borrow. Returns fewer if the heap runs out, and an empty
+/// Vec for `k <= 0`; the remaining entries pop in order as usual.
+pub fn heap_pop_many<T: Ord>(h: Heap<T>, k: i32) -> Vec<(i32, T)> {
+    let mut inner = h.borrow_mut();
+    let mut out = Vec::new();
+    for _ in 0..k.max(0) {
+        let Some((key, s)) = inner.data.pop() else { break };
+        out.push((inner.priority(key), s));
+    }
+    out
+}
+
+/// Return the `(priority, item)` pair that `heap_pop` would return next,
+/// without removing it. Returns `None` if the heap is empty.
+pub fn heap_peek<T: Ord + Clone>(h: Heap<T>) -> Option<(i32, T)> {
+    let inner = h.borrow();
+    inner.data.peek().map(|(key, s)| (inner.priority(*key), s.clone()))
+}
+
+/// Empty the heap and return every `(priority, item)` pair in pop order:
+/// ascending priority for a min-heap, descending for a max-heap. Uses
+/// into_sorted_vec on the backing storage instead of repeated pops.
+pub fn heap_drain_sorted<T: Ord>(h: Heap<T>) -> Vec<(i32, T)> {
+    let mut inner = h.borrow_mut();
+    let data = std::mem::take(&mut inner.data);
+    sorted_entries(&inner, data)
+}
+
+/// Like `heap_drain_sorted`, but leaves the heap intact.
+pub fn heap_to_sorted_vec<T: Ord + Clone>(h: Heap<T>) -> Vec<(i32, T)> {
+    let inner = h.borrow();
+    sorted_entries(&inner, inner.data.clone())
+}
+
+/// into_sorted_vec is ascending by key; pop order is descending by key.
+fn sorted_entries<T: Ord>(inner: &HeapInner<T>, data: BinaryHeap<(i64, T)>) -> Vec<(i32, T)> {
+    data.into_sorted_vec()
+        .into_iter()
+        .rev()
+        .map(|(key, s)| (inner.priority(key), s))
+        .collect()
 }
 
-/// Pop and return the `(priority, item)` pair with the lowest priority.
-/// Returns `None` if the heap is empty.
-pub fn heap_pop(h: Heap) -> Option<(i32, String)> {
-    h.borrow_mut().pop().map(|(Reverse(p), s)| (p, s))
+/// Return an independent copy of the heap: a new Rc<RefCell<...>> with the
+/// same entries and ordering, so pushes/pops on the copy leave the original
+/// untouched (unlike `h.clone()`, which shares state).
+pub fn heap_clone_deep<T: Ord + Clone>(h: Heap<T>) -> Heap<T> {
+    let inner = h.borrow();
+    Rc::new(RefCell::new(HeapInner { data: inner.data.clone(), max: inner.max }))
+}
+
+/// Return a snapshot of the current `(priority, item)` pairs in no
+/// particular order, without draining the heap.
+pub fn heap_items<T: Ord + Clone>(h: Heap<T>) -> Vec<(i32, T)> {
+    let inner = h.borrow();
+    inner.data.iter().map(|(key, s)| (inner.priority(*key), s.clone())).collect()
 }
 
 /// Return the number of items in the heap (i32 for .hom int compatibility).
-pub fn heap_len(h: Heap) -> i32 {
-    h.borrow().len() as i32
+pub fn heap_len<T>(h: Heap<T>) -> i32 {
+    h.borrow().data.len() as i32
 }
 
 /// Return `true` if the heap contains no items.
-pub fn heap_is_empty(h: Heap) -> bool {
-    h.borrow().is_empty()
+pub fn heap_is_empty<T>(h: Heap<T>) -> bool {
+    h.borrow().data.is_empty()
 }
 
 #[cfg(test)]
@@ -159,6 +367,40 @@ mod tests {
         assert!(heap_is_empty(h.clone()));
     }
 
+    // ── heap_peek ───────────────────────────────────────────
+    #[test]
+    fn test_heap_peek_empty_returns_none() {
+        let h = heap_new();
+        assert_eq!(heap_peek(h.clone()), None);
+    }
+
+    #[test]
+    fn test_heap_peek_does_not_remove() {
+        let h = heap_new();
+        heap_push(h.clone(), 4, "four");
+        heap_push(h.clone(), 1, "one");
+        assert_eq!(heap_peek(h.clone()), Some((1, "one".to_string())));
+        assert_eq!(heap_len(h.clone()), 2);
+    }
+
+    #[test]
+    fn test_heap_peek_matches_next_pop() {
+        let h = heap_new();
+        heap_push(h.clone(), 5, "five");
+        heap_push(h.clone(), 2, "two");
+        heap_push(h.clone(), 8, "eight");
+        let mut pops = Vec::new();
+        while let Some(peeked) = heap_peek(h.clone()) {
+            let popped = heap_pop(h.clone()).unwrap();
+            assert_eq!(peeked, popped);
+            pops.push(popped.0);
+            if popped.0 == 2 {
+                heap_push(h.clone(), 3, "three");
+            }
+        }
+        assert_eq!(pops, vec![2, 3, 5, 8]);
+    }
+
     // ── negative and zero priorities ────────────────────────
     #[test]
     fn test_heap_negative_priorities() {
@@ -231,4 +473,435 @@ mod tests {
         assert_eq!(heap_pop(h2.clone()), Some((7, "seven".to_string())));
         assert!(heap_is_empty(h1.clone()));
     }
+
+    // ── max-heap ────────────────────────────────────────────
+    #[test]
+    fn test_max_heap_pops_largest_first() {
+        let h = heap_new_max();
+        heap_push(h.clone(), 5, "five");
+        heap_push(h.clone(), 1, "one");
+        heap_push(h.clone(), 9, "nine");
+        heap_push(h.clone(), 3, "three");
+        let mut pops: Vec<i32> = Vec::new();
+        while let Some((p, _)) = heap_pop(h.clone()) {
+            pops.push(p);
+        }
+        assert_eq!(pops, vec![9, 5, 3, 1]);
+    }
+
+    #[test]
+    fn test_max_heap_peek_len_is_empty() {
+        let h = heap_new_max();
+        assert!(heap_is_empty(h.clone()));
+        assert_eq!(heap_peek(h.clone()), None);
+        heap_push(h.clone(), -4, "neg");
+        heap_push(h.clone(), 2, "pos");
+        assert_eq!(heap_len(h.clone()), 2);
+        assert_eq!(heap_peek(h.clone()), Some((2, "pos".to_string())));
+    }
+
+    #[test]
+    fn test_max_heap_extreme_priorities() {
+        let h = heap_new_max();
+        heap_push(h.clone(), i32::MIN, "min");
+        heap_push(h.clone(), i32::MAX, "max");
+        heap_push(h.clone(), 0, "zero");
+        assert_eq!(heap_pop(h.clone()), Some((i32::MAX, "max".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((0, "zero".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((i32::MIN, "min".to_string())));
+    }
+
+    #[test]
+    fn test_min_heap_extreme_priorities() {
+        let h = heap_new();
+        heap_push(h.clone(), i32::MAX, "max");
+        heap_push(h.clone(), i32::MIN, "min");
+        assert_eq!(heap_pop(h.clone()), Some((i32::MIN, "min".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((i32::MAX, "max".to_string())));
+    }
+
+    #[test]
+    fn test_min_and_max_handles_keep_own_order() {
+        let lo = heap_new();
+        let hi = heap_new_max();
+        for (p, s) in [(3, "c"), (1, "a"), (2, "b")] {
+            heap_push(lo.clone(), p, s);
+            heap_push(hi.clone(), p, s);
+        }
+        assert_eq!(heap_pop(lo.clone()), Some((1, "a".to_string())));
+        assert_eq!(heap_pop(hi.clone()), Some((3, "c".to_string())));
+        let hi2 = hi.clone();
+        assert_eq!(heap_pop(hi2), Some((2, "b".to_string())));
+    }
+
+    // ── generic items ───────────────────────────────────────
+    #[test]
+    fn test_heap_i32_items() {
+        let h = heap_new_of::<i32>();
+        heap_push(h.clone(), 3, 30);
+        heap_push(h.clone(), 1, 10);
+        heap_push(h.clone(), 2, 20);
+        assert_eq!(heap_peek(h.clone()), Some((1, 10)));
+        assert_eq!(heap_pop(h.clone()), Some((1, 10)));
+        assert_eq!(heap_pop(h.clone()), Some((2, 20)));
+        assert_eq!(heap_len(h.clone()), 1);
+    }
+
+    #[test]
+    fn test_heap_tuple_items_dijkstra_grid() {
+        let frontier: Heap<(i32, i32)> = heap_new_of();
+        heap_push(frontier.clone(), 7, (2, 3));
+        heap_push(frontier.clone(), 0, (0, 0));
+        heap_push(frontier.clone(), 4, (1, 1));
+        let mut cells = Vec::new();
+        while let Some((_, cell)) = heap_pop(frontier.clone()) {
+            cells.push(cell);
+        }
+        assert_eq!(cells, vec![(0, 0), (1, 1), (2, 3)]);
+    }
+
+    #[test]
+    fn test_heap_generic_max_and_shared_handle() {
+        let h1 = heap_new_max_of::<(i32, i32)>();
+        let h2 = h1.clone();
+        heap_push(h1.clone(), 1, (1, 1));
+        heap_push(h1.clone(), 9, (9, 9));
+        assert_eq!(heap_pop(h2.clone()), Some((9, (9, 9))));
+        assert_eq!(heap_len(h1.clone()), 1);
+    }
+
+    // Verify &String works too (codegen may pass borrowed Strings)
+    #[test]
+    fn test_heap_push_borrowed_string() {
+        let h = heap_new();
+        let name = String::from("node");
+        heap_push(h.clone(), 1, &name);
+        assert_eq!(heap_pop(h.clone()), Some((1, name)));
+    }
+
+    // ── heap_from_pairs / heap_push_all ─────────────────────
+    #[test]
+    fn test_heap_from_pairs_empty() {
+        let h: Heap = heap_from_pairs(vec![]);
+        assert!(heap_is_empty(h.clone()));
+        assert_eq!(heap_pop(h.clone()), None);
+    }
+
+    #[test]
+    fn test_heap_from_pairs_matches_pushes() {
+        let pairs = vec![
+            (4, "d".to_string()),
+            (-1, "z".to_string()),
+            (9, "i".to_string()),
+            (0, "o".to_string()),
+            (2, "b".to_string()),
+        ];
+        let built = heap_from_pairs(pairs.clone());
+        let pushed = heap_new();
+        for (p, item) in pairs {
+            heap_push(pushed.clone(), p, item);
+        }
+        assert_eq!(heap_len(built.clone()), 5);
+        while let Some(entry) = heap_pop(pushed.clone()) {
+            assert_eq!(heap_pop(built.clone()), Some(entry));
+        }
+        assert!(heap_is_empty(built.clone()));
+    }
+
+    #[test]
+    fn test_heap_push_all_extends_existing() {
+        let h = heap_new();
+        heap_push(h.clone(), 5, "mid");
+        heap_push_all(h.clone(), vec![(1, "low".to_string()), (8, "high".to_string())]);
+        assert_eq!(heap_len(h.clone()), 3);
+        assert_eq!(heap_pop(h.clone()), Some((1, "low".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((5, "mid".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((8, "high".to_string())));
+    }
+
+    #[test]
+    fn test_heap_push_all_respects_max_heap() {
+        let h = heap_new_max();
+        heap_push_all(h.clone(), vec![(1, "low".to_string()), (8, "high".to_string())]);
+        assert_eq!(heap_pop(h.clone()), Some((8, "high".to_string())));
+    }
+
+    // ── heap_contains / heap_remove ─────────────────────────
+    #[test]
+    fn test_heap_contains() {
+        let h = heap_new();
+        heap_push(h.clone(), 3, "a");
+        heap_push(h.clone(), 7, "b");
+        assert!(heap_contains(h.clone(), "a"));
+        assert!(heap_contains(h.clone(), "b"));
+        assert!(!heap_contains(h.clone(), "c"));
+        assert!(!heap_contains(heap_new(), "a"));
+    }
+
+    #[test]
+    fn test_heap_remove_middle_preserves_order() {
+        let h = heap_new();
+        for (p, item) in [(5, "e"), (1, "a"), (3, "c"), (2, "b"), (4, "d")] {
+            heap_push(h.clone(), p, item);
+        }
+        assert!(heap_remove(h.clone(), "c"));
+        assert_eq!(heap_len(h.clone()), 4);
+        assert!(!heap_contains(h.clone(), "c"));
+        let mut order = Vec::new();
+        while let Some((p, item)) = heap_pop(h.clone()) {
+            order.push((p, item));
+        }
+        assert_eq!(
+            order,
+            vec![
+                (1, "a".to_string()),
+                (2, "b".to_string()),
+                (4, "d".to_string()),
+                (5, "e".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_heap_remove_missing_is_noop() {
+        let h = heap_new();
+        heap_push(h.clone(), 2, "x");
+        heap_push(h.clone(), 1, "y");
+        assert!(!heap_remove(h.clone(), "zzz"));
+        assert_eq!(heap_len(h.clone()), 2);
+        assert_eq!(heap_peek(h.clone()), Some((1, "y".to_string())));
+    }
+
+    #[test]
+    fn test_heap_remove_only_one_duplicate() {
+        let h = heap_new();
+        heap_push(h.clone(), 1, "dup");
+        heap_push(h.clone(), 9, "dup");
+        assert!(heap_remove(h.clone(), "dup"));
+        assert_eq!(heap_len(h.clone()), 1);
+        assert!(heap_contains(h.clone(), "dup"));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_heap_remove_string_type() {
+        let h = heap_new();
+        heap_push(h.clone(), 1, "node");
+        let item = String::from("node");
+        assert!(heap_remove(h.clone(), item));
+        assert!(heap_is_empty(h.clone()));
+    }
+
+    // ── heap_update_priority ────────────────────────────────
+    #[test]
+    fn test_heap_update_priority_dijkstra() {
+        let h = heap_new();
+        heap_push(h.clone(), 10, "far");
+        heap_push(h.clone(), 5, "mid");
+        heap_push(h.clone(), 12, "other");
+        // shorter path to "far" found
+        assert!(heap_update_priority(h.clone(), "far", 3));
+        assert_eq!(heap_len(h.clone()), 3);
+        assert_eq!(heap_pop(h.clone()), Some((3, "far".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((5, "mid".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((12, "other".to_string())));
+    }
+
+    #[test]
+    fn test_heap_update_priority_missing() {
+        let h = heap_new();
+        heap_push(h.clone(), 1, "a");
+        assert!(!heap_update_priority(h.clone(), "b", 0));
+        assert_eq!(heap_peek(h.clone()), Some((1, "a".to_string())));
+    }
+
+    #[test]
+    fn test_heap_update_priority_lowest_duplicate() {
+        let h = heap_new();
+        heap_push(h.clone(), 8, "dup");
+        heap_push(h.clone(), 4, "dup");
+        heap_push(h.clone(), 6, "x");
+        assert!(heap_update_priority(h.clone(), "dup", 7));
+        assert_eq!(heap_pop(h.clone()), Some((6, "x".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((7, "dup".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((8, "dup".to_string())));
+    }
+
+    #[test]
+    fn test_heap_update_priority_max_heap() {
+        let h = heap_new_max();
+        heap_push(h.clone(), 1, "a");
+        heap_push(h.clone(), 5, "b");
+        assert!(heap_update_priority(h.clone(), "a", 9));
+        assert_eq!(heap_pop(h.clone()), Some((9, "a".to_string())));
+    }
+
+    // ── heap_clear / heap_reserve ───────────────────────────
+    #[test]
+    fn test_heap_clear_then_reuse() {
+        let h = heap_new();
+        for i in 0..100 {
+            heap_push(h.clone(), 100 - i, format!("n{}", i));
+        }
+        let cap = h.borrow().data.capacity();
+        heap_clear(h.clone());
+        assert_eq!(heap_len(h.clone()), 0);
+        assert!(heap_is_empty(h.clone()));
+        assert_eq!(h.borrow().data.capacity(), cap);
+        heap_push(h.clone(), 3, "c");
+        heap_push(h.clone(), 1, "a");
+        heap_push(h.clone(), 2, "b");
+        assert_eq!(heap_pop(h.clone()), Some((1, "a".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((2, "b".to_string())));
+        assert_eq!(heap_pop(h.clone()), Some((3, "c".to_string())));
+    }
+
+    #[test]
+    fn test_heap_clear_through_clone() {
+        let h1 = heap_new();
+        let h2 = h1.clone();
+        heap_push(h1.clone(), 1, "x");
+        heap_clear(h2.clone());
+        assert!(heap_is_empty(h1.clone()));
+    }
+
+    #[test]
+    fn test_heap_reserve() {
+        let h = heap_new();
+        let h2 = h.clone();
+        heap_reserve(h2.clone(), 64);
+        assert!(h.borrow().data.capacity() >= 64);
+        assert!(heap_is_empty(h.clone()));
+        heap_reserve(h.clone(), -5);
+        heap_push(h.clone(), 1, "a");
+        assert_eq!(heap_len(h.clone()), 1);
+    }
+
+    // ── heap_drain_sorted / heap_to_sorted_vec ──────────────
+    #[test]
+    fn test_heap_drain_sorted() {
+        let h = heap_new();
+        for (p, item) in [(4, "d"), (1, "a"), (3, "c"), (2, "b")] {
+            heap_push(h.clone(), p, item);
+        }
+        let v = heap_drain_sorted(h.clone());
+        let priorities: Vec<i32> = v.iter().map(|(p, _)| *p).collect();
+        assert_eq!(priorities, vec![1, 2, 3, 4]);
+        assert_eq!(v[0], (1, "a".to_string()));
+        assert!(heap_is_empty(h.clone()));
+    }
+
+    #[test]
+    fn test_heap_to_sorted_vec_keeps_heap() {
+        let h = heap_new();
+        heap_push(h.clone(), 9, "z");
+        heap_push(h.clone(), -2, "m");
+        heap_push(h.clone(), 5, "q");
+        let v = heap_to_sorted_vec(h.clone());
+        assert_eq!(
+            v,
+            vec![(-2, "m".to_string()), (5, "q".to_string()), (9, "z".to_string())]
+        );
+        assert_eq!(heap_len(h.clone()), 3);
+        assert_eq!(heap_pop(h.clone()), Some((-2, "m".to_string())));
+    }
+
+    #[test]
+    fn test_heap_sorted_matches_pop_order() {
+        let h = heap_new();
+        for (p, item) in [(2, "b"), (2, "a"), (1, "x"), (2, "c")] {
+            heap_push(h.clone(), p, item);
+        }
+        let snapshot = heap_to_sorted_vec(h.clone());
+        let mut popped = Vec::new();
+        while let Some(entry) = heap_pop(h.clone()) {
+            popped.push(entry);
+        }
+        assert_eq!(snapshot, popped);
+    }
+
+    #[test]
+    fn test_heap_sorted_empty_and_max() {
+        assert!(heap_drain_sorted(heap_new()).is_empty());
+        assert!(heap_to_sorted_vec(heap_new()).is_empty());
+        let h = heap_new_max();
+        heap_push(h.clone(), 1, "lo");
+        heap_push(h.clone(), 7, "hi");
+        assert_eq!(
+            heap_drain_sorted(h.clone()),
+            vec![(7, "hi".to_string()), (1, "lo".to_string())]
+        );
+    }
+
+    // ── heap_pop_many ───────────────────────────────────────
+    #[test]
+    fn test_heap_pop_many_then_single_pops() {
+        let h = heap_new();
+        for (p, item) in [(5, "e"), (1, "a"), (4, "d"), (2, "b"), (3, "c")] {
+            heap_push(h.clone(), p, item);
+        }
+        let top = heap_pop_many(h.clone(), 2);
+        assert_eq!(top, vec![(1, "a".to_string()), (2, "b".to_string())]);
+        assert_eq!(heap_len(h.clone()), 3);
+        assert_eq!(heap_pop(h.clone()), Some((3, "c".to_string())));
+    }
+
+    #[test]
+    fn test_heap_pop_many_k_exceeds_len() {
+        let h = heap_new();
+        heap_push(h.clone(), 2, "b");
+        heap_push(h.clone(), 1, "a");
+        let all = heap_pop_many(h.clone(), 10);
+        assert_eq!(all, vec![(1, "a".to_string()), (2, "b".to_string())]);
+        assert!(heap_is_empty(h.clone()));
+    }
+
+    #[test]
+    fn test_heap_pop_many_non_positive_k() {
+        let h = heap_new();
+        heap_push(h.clone(), 1, "a");
+        assert!(heap_pop_many(h.clone(), 0).is_empty());
+        assert!(heap_pop_many(h.clone(), -3).is_empty());
+        assert_eq!(heap_len(h.clone()), 1);
+    }
+
+    // ── heap_clone_deep / heap_items ────────────────────────
+    #[test]
+    fn test_heap_clone_deep_is_independent() {
+        let h = heap_new();
+        heap_push(h.clone(), 2, "b");
+        heap_push(h.clone(), 1, "a");
+        let copy = heap_clone_deep(h.clone());
+        assert_eq!(heap_pop(copy.clone()), Some((1, "a".to_string())));
+        heap_push(copy.clone(), 0, "new");
+        assert_eq!(heap_len(h.clone()), 2);
+        assert_eq!(heap_peek(h.clone()), Some((1, "a".to_string())));
+        heap_push(h.clone(), -1, "orig");
+        assert_eq!(heap_peek(copy.clone()), Some((0, "new".to_string())));
+    }
+
+    #[test]
+    fn test_heap_clone_deep_keeps_max_order() {
+        let h = heap_new_max();
+        heap_push(h.clone(), 1, "lo");
+        heap_push(h.clone(), 9, "hi");
+        let copy = heap_clone_deep(h.clone());
+        assert_eq!(heap_pop(copy.clone()), Some((9, "hi".to_string())));
+    }
+
+    #[test]
+    fn test_heap_items_snapshot() {
+        let h = heap_new();
+        heap_push(h.clone(), 3, "c");
+        heap_push(h.clone(), 1, "a");
+        heap_push(h.clone(), 2, "b");
+        let mut items = heap_items(h.clone());
+        assert_eq!(heap_len(h.clone()), 3);
+        items.sort();
+        assert_eq!(
+            items,
+            vec![(1, "a".to_string()), (2, "b".to_string()), (3, "c".to_string())]
+        );
+        assert!(heap_items(heap_new()).is_empty());
+    }
 }
@@ -0,0 +1,220 @@
+// ============================================================
+// Homun Runtime — queue.rs: Shared FIFO Queue
+// stdlib, no external crates required.
+//
+// Usage in .hom:
+//   use queue
+//
+//   q := queue_new()
+//   queue_push(q, "start")
+//   ok, node := queue_pop(q)      // (false, "") when empty
+//   ok, next := queue_peek(q)     // look without consuming
+//   n := queue_len(q)
+//   empty := queue_is_empty(q)
+//
+//   iq := iqueue_new()            // same API over int payloads
+//   iqueue_push(iq, 7)
+//
+// Implementation note:
+//   Same sharing model as heap.rs: Rc<RefCell<VecDeque<...>>> so that
+//   Homun's clone-based calling convention (every variable argument
+//   becomes arg.clone()) still refers to the SAME underlying queue.
+//   A queue passed into a .hom function and pushed to there is
+//   visible to the caller, unlike the plain VecDeque helpers in
+//   std/deque.rs.
+//
+//   Lengths use i32 to match .hom's int type.  item accepts
+//   impl AsRef<str> so &str literals (Rust tests) and String values
+//   (homunc codegen) both work.
+// ============================================================
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// FIFO queue of String items shared through Rc<RefCell<...>>.
+pub type Queue = Rc<RefCell<VecDeque<String>>>;
+
+/// FIFO queue of i32 items shared through Rc<RefCell<...>>.
+pub type IQueue = Rc<RefCell<VecDeque<i32>>>;
+
+/// Create a new empty queue.
+pub fn queue_new() -> Queue {
+    Rc::new(RefCell::new(VecDeque::new()))
+}
+
+/// Push `item` onto the back of the queue.
+pub fn queue_push(q: Queue, item: impl AsRef<str>) {
+    q.borrow_mut().push_back(item.as_ref().to_string());
+}
+
+/// Pop the front item. Returns `(false, "")` if the queue is empty.
+pub fn queue_pop(q: Queue) -> (bool, String) {
+    match q.borrow_mut().pop_front() {
+        Some(s) => (true, s),
+        None => (false, String::new()),
+    }
+}
+
+/// Return the front item without removing it. `(false, "")` if empty.
+pub fn queue_peek(q: Queue) -> (bool, String) {
+    match q.borrow().front() {
+        Some(s) => (true, s.clone()),
+        None => (false, String::new()),
+    }
+}
+
+/// Return the number of items in the queue (i32 for .hom int compatibility).
+pub fn queue_len(q: Queue) -> i32 {
+    q.borrow().len() as i32
+}
+
+/// Return `true` if the queue contains no items.
+pub fn queue_is_empty(q: Queue) -> bool {
+    q.borrow().is_empty()
+}
+
+/// Create a new empty int queue.
+pub fn iqueue_new() -> IQueue {
+    Rc::new(RefCell::new(VecDeque::new()))
+}
+
+/// Push `item` onto the back of the int queue.
+pub fn iqueue_push(q: IQueue, item: i32) {
+    q.borrow_mut().push_back(item);
+}
+
+/// Pop the front item. Returns `(false, 0)` if the queue is empty.
+pub fn iqueue_pop(q: IQueue) -> (bool, i32) {
+    match q.borrow_mut().pop_front() {
+        Some(x) => (true, x),
+        None => (false, 0),
+    }
+}
+
+/// Return the front item without removing it. `(false, 0)` if empty.
+pub fn iqueue_peek(q: IQueue) -> (bool, i32) {
+    match q.borrow().front() {
+        Some(x) => (true, *x),
+        None => (false, 0),
+    }
+}
+
+/// Return the number of items in the int queue.
+pub fn iqueue_len(q: IQueue) -> i32 {
+    q.borrow().len() as i32
+}
+
+/// Return `true` if the int queue contains no items.
+pub fn iqueue_is_empty(q: IQueue) -> bool {
+    q.borrow().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── queue_new ───────────────────────────────────────────
+    #[test]
+    fn test_queue_new_is_empty() {
+        let q = queue_new();
+        assert!(queue_is_empty(q.clone()));
+        assert_eq!(queue_len(q.clone()), 0);
+    }
+
+    // ── queue_push / queue_pop ──────────────────────────────
+    #[test]
+    fn test_queue_fifo_order() {
+        let q = queue_new();
+        queue_push(q.clone(), "a");
+        queue_push(q.clone(), "b");
+        queue_push(q.clone(), "c");
+        assert_eq!(queue_pop(q.clone()), (true, "a".to_string()));
+        assert_eq!(queue_pop(q.clone()), (true, "b".to_string()));
+        assert_eq!(queue_pop(q.clone()), (true, "c".to_string()));
+        assert!(queue_is_empty(q.clone()));
+    }
+
+    #[test]
+    fn test_queue_pop_empty() {
+        let q = queue_new();
+        assert_eq!(queue_pop(q.clone()), (false, String::new()));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_queue_push_string_type() {
+        let q = queue_new();
+        let item = String::from("alpha");
+        queue_push(q.clone(), item);
+        assert_eq!(queue_len(q.clone()), 1);
+    }
+
+    // ── queue_peek ──────────────────────────────────────────
+    #[test]
+    fn test_queue_peek_does_not_consume() {
+        let q = queue_new();
+        queue_push(q.clone(), "x");
+        assert_eq!(queue_peek(q.clone()), (true, "x".to_string()));
+        assert_eq!(queue_len(q.clone()), 1);
+    }
+
+    #[test]
+    fn test_queue_peek_empty() {
+        let q = queue_new();
+        assert_eq!(queue_peek(q.clone()), (false, String::new()));
+    }
+
+    // ── BFS typical usage simulation ────────────────────────
+    #[test]
+    fn test_queue_bfs_simulation() {
+        fn enqueue_children(q: Queue, node: &str) {
+            if node == "root" {
+                queue_push(q.clone(), "left");
+                queue_push(q.clone(), "right");
+            }
+        }
+        let q = queue_new();
+        queue_push(q.clone(), "root");
+        let mut order = Vec::new();
+        while let (true, node) = queue_pop(q.clone()) {
+            enqueue_children(q.clone(), &node);
+            order.push(node);
+        }
+        assert_eq!(order, vec!["root", "left", "right"]);
+    }
+
+    // ── Rc clone semantics: all handles share one queue ─────
+    #[test]
+    fn test_rc_clone_shares_state() {
+        let q1 = queue_new();
+        let q2 = q1.clone();
+        queue_push(q1.clone(), "seven");
+        assert_eq!(queue_len(q2.clone()), 1);
+        assert_eq!(queue_pop(q2.clone()), (true, "seven".to_string()));
+        assert!(queue_is_empty(q1.clone()));
+    }
+
+    // ── iqueue ──────────────────────────────────────────────
+    #[test]
+    fn test_iqueue_fifo_order() {
+        let q = iqueue_new();
+        iqueue_push(q.clone(), 3);
+        iqueue_push(q.clone(), 1);
+        assert_eq!(iqueue_peek(q.clone()), (true, 3));
+        assert_eq!(iqueue_pop(q.clone()), (true, 3));
+        assert_eq!(iqueue_pop(q.clone()), (true, 1));
+        assert_eq!(iqueue_pop(q.clone()), (false, 0));
+        assert!(iqueue_is_empty(q.clone()));
+    }
+
+    #[test]
+    fn test_iqueue_rc_clone_shares_state() {
+        let q1 = iqueue_new();
+        let q2 = q1.clone();
+        iqueue_push(q1.clone(), 42);
+        assert_eq!(iqueue_len(q2.clone()), 1);
+        assert_eq!(iqueue_pop(q2.clone()), (true, 42));
+        assert!(iqueue_is_empty(q1.clone()));
+    }
+}
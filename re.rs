@@ -19,46 +19,312 @@
 // API:
 //   re_match(pattern, text, pos) -> (bool, String, int)
 //     Anchored match at byte offset `pos`.  Returns (matched, matched_str, end_pos).
+//     Compiled as \A(?:pattern), so a failed match costs O(1) rather than
+//     a scan of the rest of text — safe to call at every position.
 //     Equivalent to Python's re.compile(pattern).match(text, pos).
 //     pos and end_pos use i32 to match .hom's int type.
 //
+//   re_match_chars(pattern, text, char_pos) -> (bool, String, int)
+//     Same as re_match, but char_pos and end_pos are character indices,
+//     matching chars() / char_at.  Use this on non-ASCII text.
+//
+//   re_try_match(pattern, text, pos) -> (bool, String, int, String)
+//     Same as re_match plus an error message (empty on success).
+//     Never panics: invalid patterns and bad positions are reported in
+//     the message instead.  Use for user-supplied patterns.
+//
 //   re_is_match(pattern, text) -> bool
 //     True if pattern matches anywhere in text.
 //     Equivalent to Python's re.search(pattern, text) is not None.
 //
-// Both functions accept impl AsRef<str> for pattern and text, so they
+//   re_sub_fn(pattern, text, f) -> String
+//     Replace every non-overlapping match with f(matched_text).
+//     Text between matches is copied unchanged.
+//
+//   re_replace_n(pattern, replacement, text, n) -> String
+//     Replace at most the first n matches (n <= 0 replaces nothing).
+//     replacement supports $1 / ${name} group references.
+//
+//   re_split_keep(pattern, text) -> [String]
+//     Split text on pattern, keeping each matched delimiter as its own
+//     element: [seg, delim, seg, delim, ..., seg].  Empty segments are kept.
+//
+//   re_is_valid(pattern) -> bool
+//     True if pattern compiles.  Never panics; use it to check
+//     user-supplied patterns before calling the functions above.
+//
+//   re_compile_error(pattern) -> String
+//     The regex crate's error message, or "" for a valid pattern.
+//
+//   re_escape(s) -> String
+//     Escape every regex metacharacter so the result matches s literally.
+//     Equivalent to Python's re.escape(s).
+//
+// All functions accept impl AsRef<str> for pattern and text, so they
 // work with both &str literals (Rust tests) and String values (homunc
 // codegen emits .to_string() on string literals when passing as args).
 //
+// Precompiled patterns:
+//   p := re_compile("[a-z]+")       // Pattern handle, compiled once
+//   matched, text, end := pat_match(p, src, pos)
+//   ok := pat_is_match(p, src)
+//
+//   Pattern is Rc<CompiledPattern>, holding two Rc<Regex>: the plain
+//   regex used for searching (pat_is_match) and the anchored \A(?:p)
+//   form used by pat_match.  .hom's clone-based calling convention
+//   passes a cheap reference-count increment (same idea as heap.rs).
+//   pat_* calls skip the cache lookup (no pattern-string hashing).
+//   re_compile goes through the cache for both forms, so one call can
+//   fill two LRU cache slots.
+//
 // Caching:
 //   Patterns are compiled once per thread and cached in a thread-local
-//   HashMap<String, Regex>.  Subsequent calls with the same pattern string
-//   reuse the compiled Regex object.
+//   LRU map.  Subsequent calls with the same pattern string reuse the
+//   compiled Regex object.  The cache holds at most 256 patterns by
+//   default; when full, the least-recently-used pattern is evicted.
+//
+//   re_cache_set_cap(n)   change the cap (0 disables caching)
+//   re_cache_clear()      drop every cached pattern and reset stats
+//   re_cache_len() -> int number of cached patterns
+//   re_cache_stats() -> (int, int)   (hits, misses) since last clear
 // ============================================================
 
 use regex::Regex;
 use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Default maximum number of compiled patterns kept per thread.
+pub const RE_CACHE_DEFAULT_CAP: i32 = 256;
+
+/// Thread-local LRU cache of compiled patterns.
+/// Each entry records the tick of its last use; when the cache is full the
+/// entry with the oldest tick is evicted.
+struct RegexCache {
+    map: std::collections::HashMap<String, (Rc<Regex>, u64)>,
+    cap: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl RegexCache {
+    fn new() -> Self {
+        RegexCache {
+            map: std::collections::HashMap::new(),
+            cap: RE_CACHE_DEFAULT_CAP as usize,
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Look up `pattern`, marking it as most recently used.
+    fn get(&mut self, pattern: &str) -> Option<Rc<Regex>> {
+        self.tick += 1;
+        let tick = self.tick;
+        match self.map.get_mut(pattern) {
+            Some((re, last)) => {
+                *last = tick;
+                self.hits += 1;
+                // Hand out the shared Rc: cloning the Regex itself would give
+                // every call a fresh, empty matching-cache pool.
+                Some(Rc::clone(re))
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Insert a freshly compiled pattern, evicting down to the cap first.
+    fn insert(&mut self, pattern: &str, re: Rc<Regex>) {
+        if self.cap == 0 {
+            return;
+        }
+        self.evict_to(self.cap - 1);
+        self.tick += 1;
+        self.map.insert(pattern.to_string(), (re, self.tick));
+    }
+
+    /// Drop least-recently-used entries until at most `n` remain.
+    fn evict_to(&mut self, n: usize) {
+        while self.map.len() > n {
+            let oldest = self
+                .map
+                .iter()
+                .min_by_key(|(_, (_, last))| *last)
+                .map(|(k, _)| k.clone())
+                .unwrap();
+            self.map.remove(&oldest);
+        }
+    }
+}
 
 thread_local! {
-    static REGEX_CACHE: RefCell<std::collections::HashMap<String, Regex>> =
-        RefCell::new(std::collections::HashMap::new());
+    static REGEX_CACHE: RefCell<RegexCache> = RefCell::new(RegexCache::new());
 }
 
 /// Get or compile a Regex for `pattern`, using the thread-local cache.
 /// Panics with a clear message if the pattern is invalid.
-fn get_or_compile(pattern: &str) -> Regex {
+fn get_or_compile(pattern: &str) -> Rc<Regex> {
+    try_compile(pattern)
+        .unwrap_or_else(|e| panic!("re: invalid regex pattern {:?}: {}", pattern, e))
+}
+
+/// Compile `pattern` without panicking.
+/// Valid patterns are added to the cache; invalid ones are never cached.
+fn try_compile(pattern: &str) -> Result<Rc<Regex>, regex::Error> {
     REGEX_CACHE.with(|cache| {
-        let mut map = cache.borrow_mut();
-        if !map.contains_key(pattern) {
-            let re = Regex::new(pattern)
-                .unwrap_or_else(|e| panic!("re: invalid regex pattern {:?}: {}", pattern, e));
-            map.insert(pattern.to_string(), re);
+        let mut cache = cache.borrow_mut();
+        if let Some(re) = cache.get(pattern) {
+            return Ok(re);
         }
-        // Regex is cheap to clone (Arc-backed)
-        map.get(pattern).unwrap().clone()
+        let re = Rc::new(Regex::new(pattern)?);
+        cache.insert(pattern, Rc::clone(&re));
+        Ok(re)
     })
 }
 
+/// Source of the anchored form of `pattern`: it can only match at the start
+/// of the haystack, so a failed match is rejected without scanning ahead.
+fn anchored_source(pattern: &str) -> String {
+    format!(r"\A(?:{})", pattern)
+}
+
+/// Get or compile the anchored form of `pattern`, cached under its own key.
+/// Panics with a clear message (naming the original pattern) if it is invalid.
+fn get_or_compile_anchored(pattern: &str) -> Rc<Regex> {
+    try_compile_anchored(pattern)
+        .unwrap_or_else(|e| panic!("re: invalid regex pattern {:?}: {}", pattern, e))
+}
+
+/// Compile the anchored form of `pattern` without panicking.
+/// `pattern` itself is validated first, since wrapping can turn an invalid
+/// pattern such as `)(?:x` into a valid one.
+fn try_compile_anchored(pattern: &str) -> Result<Rc<Regex>, regex::Error> {
+    let key = anchored_source(pattern);
+    let cached = REGEX_CACHE.with(|cache| cache.borrow_mut().get(&key));
+    if let Some(re) = cached {
+        return Ok(re);
+    }
+    Regex::new(pattern)?;
+    // A valid pattern only fails once wrapped if a trailing `#` comment under
+    // (?x) swallowed the closing paren; a newline ends the comment, and (?x)
+    // ignores it as whitespace.
+    let re = match Regex::new(&key) {
+        Ok(re) => re,
+        Err(_) => Regex::new(&format!("\\A(?:{}\n)", pattern))?,
+    };
+    let re = Rc::new(re);
+    REGEX_CACHE.with(|cache| cache.borrow_mut().insert(&key, Rc::clone(&re)));
+    Ok(re)
+}
+
+/// Set the maximum number of cached patterns for this thread.
+/// Shrinking the cap evicts least-recently-used entries immediately.
+/// A cap of 0 disables caching; negative values are treated as 0.
+pub fn re_cache_set_cap(cap: i32) {
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.cap = cap.max(0) as usize;
+        let cap = cache.cap;
+        cache.evict_to(cap);
+    })
+}
+
+/// Remove every compiled pattern from this thread's cache and reset the stats.
+/// The cap is kept.
+pub fn re_cache_clear() {
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        cache.map.clear();
+        cache.hits = 0;
+        cache.misses = 0;
+    })
+}
+
+/// Return the number of compiled patterns currently cached on this thread.
+pub fn re_cache_len() -> i32 {
+    REGEX_CACHE.with(|cache| cache.borrow().map.len() as i32)
+}
+
+/// Return `(hits, misses)` for this thread's cache since the last clear.
+pub fn re_cache_stats() -> (i32, i32) {
+    REGEX_CACHE.with(|cache| {
+        let cache = cache.borrow();
+        (cache.hits as i32, cache.misses as i32)
+    })
+}
+
+/// Replace every non-overlapping match of `pattern` in `text` with `f(match)`.
+///
+/// `f` receives the matched text by value (homunc closures take owned args)
+/// and returns its replacement. Unmatched text is preserved byte-for-byte.
+pub fn re_sub_fn(pattern: impl AsRef<str>, text: impl AsRef<str>, f: impl Fn(String) -> String) -> String {
+    let re = get_or_compile(pattern.as_ref());
+    re.replace_all(text.as_ref(), |caps: &regex::Captures| f(caps[0].to_string()))
+        .into_owned()
+}
+
+/// Replace at most the first `n` matches of `pattern` in `text` with `replacement`.
+///
+/// `replacement` supports the regex crate's `$1` / `${name}` group syntax.
+/// `n <= 0` returns `text` unchanged (note: the regex crate itself treats a
+/// limit of 0 as "replace all", so that case is handled here).
+pub fn re_replace_n(pattern: impl AsRef<str>, replacement: impl AsRef<str>, text: impl AsRef<str>, n: i32) -> String {
+    let text = text.as_ref();
+    if n <= 0 {
+        return text.to_string();
+    }
+    let re = get_or_compile(pattern.as_ref());
+    re.replacen(text, n as usize, replacement.as_ref()).into_owned()
+}
+
+/// Split `text` on `pattern`, keeping the delimiters.
+///
+/// Returns the segments and matched delimiters interleaved, always starting
+/// and ending with a segment, so the result has `2 * matches + 1` elements.
+/// Empty segments (e.g. between adjacent delimiters) are preserved.
+/// Like Python's `re.split` with the whole pattern in a capturing group.
+pub fn re_split_keep(pattern: impl AsRef<str>, text: impl AsRef<str>) -> Vec<String> {
+    let text = text.as_ref();
+    let re = get_or_compile(pattern.as_ref());
+    let mut out = Vec::new();
+    let mut last = 0;
+    for m in re.find_iter(text) {
+        out.push(text[last..m.start()].to_string());
+        out.push(m.as_str().to_string());
+        last = m.end();
+    }
+    out.push(text[last..].to_string());
+    out
+}
+
+/// Return `true` if `pattern` is a valid regex.
+///
+/// Never panics. Failed patterns are not inserted into the cache.
+pub fn re_is_valid(pattern: impl AsRef<str>) -> bool {
+    try_compile(pattern.as_ref()).is_ok()
+}
+
+/// Return the compile error message for `pattern`, or `""` if it is valid.
+///
+/// Never panics. Lets .hom code report why a user-supplied pattern was rejected.
+pub fn re_compile_error(pattern: impl AsRef<str>) -> String {
+    match try_compile(pattern.as_ref()) {
+        Ok(_) => String::new(),
+        Err(e) => e.to_string(),
+    }
+}
+
+/// Escape all regex metacharacters in `s` so the result matches `s` literally.
+///
+/// Alphanumerics are left untouched. Equivalent to Python's `re.escape(s)`.
+pub fn re_escape(s: impl AsRef<str>) -> String {
+    regex::escape(s.as_ref())
+}
+
 /// Match `pattern` anchored at byte offset `pos` in `text`.
 ///
 /// Returns `(matched, captured_text, end_pos)` where:
@@ -71,16 +337,47 @@ fn get_or_compile(pattern: &str) -> Regex {
 ///
 /// Equivalent to Python's `re.compile(pattern).match(text, pos)`.
 pub fn re_match(pattern: impl AsRef<str>, text: impl AsRef<str>, pos: i32) -> (bool, String, i32) {
-    let pattern = pattern.as_ref();
     let text = text.as_ref();
-    let pos = pos as usize;
-    if pos > text.len() {
-        return (false, String::new(), pos as i32);
+    if pos as usize > text.len() {
+        return (false, String::new(), pos);
     }
-    let re = get_or_compile(pattern);
+    let anchored = get_or_compile_anchored(pattern.as_ref());
+    match_at(&anchored, text, pos)
+}
+
+/// Non-panicking [`re_match`] for user-supplied patterns.
+///
+/// Returns `(matched, captured_text, end_pos, error)`. `error` is empty on
+/// success; otherwise it holds the compile error (or a position error) and
+/// the match fields are `(false, "", pos)`. Invalid patterns are not cached.
+pub fn re_try_match(pattern: impl AsRef<str>, text: impl AsRef<str>, pos: i32) -> (bool, String, i32, String) {
+    let text = text.as_ref();
+    let anchored = match try_compile_anchored(pattern.as_ref()) {
+        Ok(re) => re,
+        Err(e) => return (false, String::new(), pos, e.to_string()),
+    };
+    if pos < 0 || pos as usize > text.len() {
+        let msg = format!("re: pos {} out of range 0..={}", pos, text.len());
+        return (false, String::new(), pos, msg);
+    }
+    if !text.is_char_boundary(pos as usize) {
+        let msg = format!("re: pos {} is not on a character boundary", pos);
+        return (false, String::new(), pos, msg);
+    }
+    let (matched, m, end) = match_at(&anchored, text, pos);
+    (matched, m, end, String::new())
+}
+
+/// Match the `\A`-anchored regex `anchored` at byte offset `pos`
+/// (already bounds-checked). The anchor makes the engine give up at the
+/// first byte that cannot start a match instead of scanning the rest of
+/// `text`. Leftmost-first semantics mean this returns exactly the match
+/// an unanchored `find` would report when that match starts at `pos`.
+fn match_at(anchored: &Regex, text: &str, pos: i32) -> (bool, String, i32) {
+    let pos = pos as usize;
     let haystack = &text[pos..];
-    match re.find(haystack) {
-        Some(m) if m.start() == 0 => {
+    match anchored.find(haystack) {
+        Some(m) => {
             let matched_str = m.as_str().to_string();
             let end = (pos + m.end()) as i32;
             (true, matched_str, end)
@@ -89,6 +386,70 @@ pub fn re_match(pattern: impl AsRef<str>, text: impl AsRef<str>, pos: i32) -> (b
     }
 }
 
+/// Compiled forms of one pattern: `re` for searching, `anchored` for
+/// [`pat_match`].
+pub struct CompiledPattern {
+    pub re: Rc<Regex>,
+    pub anchored: Rc<Regex>,
+}
+
+/// Precompiled regex handle. `Rc` so that cloning a handle (which homunc
+/// does for every argument) is a reference-count increment, not a recompile.
+pub type Pattern = Rc<CompiledPattern>;
+
+/// Compile `pattern` into a reusable [`Pattern`] handle.
+/// Panics with a clear message if the pattern is invalid.
+pub fn re_compile(pattern: impl AsRef<str>) -> Pattern {
+    let pattern = pattern.as_ref();
+    Rc::new(CompiledPattern {
+        re: get_or_compile(pattern),
+        anchored: get_or_compile_anchored(pattern),
+    })
+}
+
+/// [`re_match`] on a precompiled [`Pattern`]; no cache lookup.
+pub fn pat_match(p: Pattern, text: impl AsRef<str>, pos: i32) -> (bool, String, i32) {
+    let text = text.as_ref();
+    if pos as usize > text.len() {
+        return (false, String::new(), pos);
+    }
+    match_at(&p.anchored, text, pos)
+}
+
+/// [`re_is_match`] on a precompiled [`Pattern`]; no cache lookup.
+pub fn pat_is_match(p: Pattern, text: impl AsRef<str>) -> bool {
+    p.re.is_match(text.as_ref())
+}
+
+/// Character-index variant of [`re_match`].
+///
+/// `char_pos` is a character (not byte) index into `text`, and the returned
+/// end position is a character index too, so results line up with `chars()`
+/// and `char_at` on non-ASCII text. Returns not-matched (with `char_pos`
+/// echoed back) when `char_pos` is negative or past the end.
+pub fn re_match_chars(pattern: impl AsRef<str>, text: impl AsRef<str>, char_pos: i32) -> (bool, String, i32) {
+    let text = text.as_ref();
+    if char_pos < 0 {
+        return (false, String::new(), char_pos);
+    }
+    // Byte offset of every char boundary, including the end of the text.
+    let byte_pos = text
+        .char_indices()
+        .map(|(b, _)| b)
+        .chain(std::iter::once(text.len()))
+        .nth(char_pos as usize);
+    let byte_pos = match byte_pos {
+        Some(b) => b,
+        None => return (false, String::new(), char_pos),
+    };
+    let (matched, m, _) = re_match(pattern, text, byte_pos as i32);
+    if !matched {
+        return (false, String::new(), char_pos);
+    }
+    let end = char_pos + m.chars().count() as i32;
+    (true, m, end)
+}
+
 /// Return `true` if `pattern` matches anywhere in `text`.
 ///
 /// Accepts impl AsRef<str> for pattern and text.
@@ -282,4 +643,397 @@ mod tests {
         assert!(m2);
         assert_eq!(t2, "LR");
     }
+
+    // ── re_is_valid / re_compile_error ───────────────────────
+    #[test]
+    fn test_re_is_valid_good_pattern() {
+        assert!(re_is_valid("[a-z]+"));
+        assert!(re_is_valid(r"\d{2,3}"));
+    }
+
+    #[test]
+    fn test_re_is_valid_bad_pattern() {
+        assert!(!re_is_valid("[a-z"));
+        assert!(!re_is_valid("(unclosed"));
+    }
+
+    #[test]
+    fn test_re_is_valid_does_not_cache_failures() {
+        let bad = "(never_cached";
+        assert!(!re_is_valid(bad));
+        assert!(REGEX_CACHE.with(|c| !c.borrow().map.contains_key(bad)));
+    }
+
+    #[test]
+    fn test_re_compile_error_empty_for_valid() {
+        assert_eq!(re_compile_error("[0-9]+"), "");
+    }
+
+    #[test]
+    fn test_re_compile_error_message_for_invalid() {
+        let pattern = String::from("[a-z");
+        let msg = re_compile_error(pattern);
+        assert!(!msg.is_empty());
+    }
+
+    // ── re_escape ────────────────────────────────────────────
+    #[test]
+    fn test_re_escape_leaves_alnum() {
+        assert_eq!(re_escape("node_A123"), "node_A123");
+    }
+
+    #[test]
+    fn test_re_escape_dot_and_parens() {
+        assert_eq!(re_escape("a.b(c)"), r"a\.b\(c\)");
+        assert!(!re_is_match(re_escape("a.b"), "axb"));
+    }
+
+    #[test]
+    fn test_re_escape_round_trip_punctuation() {
+        let weird = r"[a-z]+ (x|y)? .*^$ {1,2} \ # & ~ - -->";
+        let pattern = format!("^{}$", re_escape(weird));
+        assert!(re_is_match(pattern, weird));
+    }
+
+    #[test]
+    fn test_re_escape_inside_class() {
+        let class = format!("^[{}]+$", re_escape("a-z"));
+        assert!(re_is_match(&class, "a-z"));
+        assert!(!re_is_match(&class, "b"));
+    }
+
+    // ── cache management ─────────────────────────────────────
+    #[test]
+    fn test_re_cache_clear_and_len() {
+        re_cache_clear();
+        assert_eq!(re_cache_len(), 0);
+        re_is_match("a+", "aaa");
+        re_is_match("b+", "bbb");
+        re_is_match("a+", "a");
+        assert_eq!(re_cache_len(), 2);
+        re_cache_clear();
+        assert_eq!(re_cache_len(), 0);
+    }
+
+    #[test]
+    fn test_re_cache_stats_hits_and_misses() {
+        re_cache_clear();
+        re_is_match("x+", "x");
+        re_is_match("x+", "xx");
+        re_is_match("x+", "xxx");
+        assert_eq!(re_cache_stats(), (2, 1));
+    }
+
+    #[test]
+    fn test_re_cache_stays_at_cap() {
+        re_cache_clear();
+        let cap = RE_CACHE_DEFAULT_CAP;
+        for i in 0..cap + 10 {
+            assert!(re_is_match(format!("p{}x", i), format!("p{}x", i)));
+        }
+        assert_eq!(re_cache_len(), cap);
+        // The most recent patterns are still cached: looking them up only hits.
+        let (hits_before, misses_before) = re_cache_stats();
+        for i in cap..cap + 10 {
+            re_is_match(format!("p{}x", i), "");
+        }
+        let (hits_after, misses_after) = re_cache_stats();
+        assert_eq!(misses_after, misses_before);
+        assert_eq!(hits_after, hits_before + 10);
+    }
+
+    #[test]
+    fn test_re_cache_hot_pattern_not_evicted() {
+        re_cache_clear();
+        re_cache_set_cap(3);
+        re_is_match("hot", "hot");
+        for i in 0..10 {
+            re_is_match(format!("cold{}", i), "");
+            re_is_match("hot", "hot");
+        }
+        assert_eq!(re_cache_len(), 3);
+        assert!(REGEX_CACHE.with(|c| c.borrow().map.contains_key("hot")));
+        re_cache_set_cap(RE_CACHE_DEFAULT_CAP);
+    }
+
+    #[test]
+    fn test_re_cache_set_cap_shrinks() {
+        re_cache_clear();
+        for p in ["a", "b", "c", "d"] {
+            re_is_match(p, p);
+        }
+        re_cache_set_cap(2);
+        assert_eq!(re_cache_len(), 2);
+        assert!(REGEX_CACHE.with(|c| c.borrow().map.contains_key("d")));
+        re_cache_set_cap(0);
+        assert_eq!(re_cache_len(), 0);
+        assert!(re_is_match("z", "z"));
+        assert_eq!(re_cache_len(), 0);
+        re_cache_set_cap(RE_CACHE_DEFAULT_CAP);
+    }
+
+    // ── re_match_chars ───────────────────────────────────────
+    #[test]
+    fn test_re_match_chars_ascii_same_as_bytes() {
+        assert_eq!(re_match_chars("[a-z]+", "hello world", 6), re_match("[a-z]+", "hello world", 6));
+    }
+
+    #[test]
+    fn test_re_match_chars_multibyte_label() {
+        let text = "café --> B";
+        let (matched, m, end) = re_match_chars(r"\w+", text, 0);
+        assert!(matched);
+        assert_eq!(m, "café");
+        assert_eq!(end, 4);
+        let (matched, m, end) = re_match_chars("-->", text, 5);
+        assert!(matched);
+        assert_eq!(m, "-->");
+        assert_eq!(end, 8);
+    }
+
+    #[test]
+    fn test_re_match_chars_after_multibyte() {
+        let (matched, m, end) = re_match_chars("[A-Z]", "日本 X", 3);
+        assert!(matched);
+        assert_eq!(m, "X");
+        assert_eq!(end, 4);
+    }
+
+    #[test]
+    fn test_re_match_chars_at_end() {
+        let (matched, _, end) = re_match_chars("[a-z]*", "né", 2);
+        assert!(matched);
+        assert_eq!(end, 2);
+    }
+
+    #[test]
+    fn test_re_match_chars_past_end() {
+        let (matched, m, end) = re_match_chars("[a-z]*", "né", 3);
+        assert!(!matched);
+        assert_eq!(m, "");
+        assert_eq!(end, 3);
+    }
+
+    #[test]
+    fn test_re_match_chars_negative_pos() {
+        let (matched, _, _) = re_match_chars("[a-z]+", "abc", -1);
+        assert!(!matched);
+    }
+
+    // ── re_sub_fn ────────────────────────────────────────────
+    #[test]
+    fn test_re_sub_fn_uppercase_identifiers() {
+        let out = re_sub_fn("[a-z_]+", "foo = bar + 1", |m: String| m.to_uppercase());
+        assert_eq!(out, "FOO = BAR + 1");
+    }
+
+    #[test]
+    fn test_re_sub_fn_no_match_unchanged() {
+        assert_eq!(re_sub_fn("[0-9]+", "héllo wörld", |_| "#".to_string()), "héllo wörld");
+    }
+
+    #[test]
+    fn test_re_sub_fn_numbers_matches_with_counter() {
+        let counter = RefCell::new(0);
+        let out = re_sub_fn("X", "X-X-X", |m: String| {
+            *counter.borrow_mut() += 1;
+            format!("{}{}", m, counter.borrow())
+        });
+        assert_eq!(out, "X1-X2-X3");
+        assert_eq!(*counter.borrow(), 3);
+    }
+
+    // ── re_replace_n ─────────────────────────────────────────
+    #[test]
+    fn test_re_replace_n_first_only() {
+        assert_eq!(re_replace_n("a", "b", "aaa", 1), "baa");
+    }
+
+    #[test]
+    fn test_re_replace_n_strip_leading_keyword() {
+        let out = re_replace_n(r"^flowchart\s*", "", "flowchart LR flowchart", 1);
+        assert_eq!(out, "LR flowchart");
+    }
+
+    #[test]
+    fn test_re_replace_n_larger_than_count() {
+        assert_eq!(re_replace_n("[0-9]", "#", "a1b2", 10), "a#b#");
+    }
+
+    #[test]
+    fn test_re_replace_n_zero_and_negative_unchanged() {
+        assert_eq!(re_replace_n("a", "b", "aaa", 0), "aaa");
+        assert_eq!(re_replace_n("a", "b", "aaa", -3), "aaa");
+    }
+
+    #[test]
+    fn test_re_replace_n_group_syntax() {
+        let out = re_replace_n(r"(\w+)=(\w+)", "$2=$1", "a=b c=d", 1);
+        assert_eq!(out, "b=a c=d");
+    }
+
+    // ── re_split_keep ────────────────────────────────────────
+    #[test]
+    fn test_re_split_keep_multi_edge_line() {
+        let parts = re_split_keep("-->|---", "A-->B---C-->D");
+        assert_eq!(parts, vec!["A", "-->", "B", "---", "C", "-->", "D"]);
+    }
+
+    #[test]
+    fn test_re_split_keep_adjacent_delimiters() {
+        let parts = re_split_keep("-->|---", "A-->---B");
+        assert_eq!(parts, vec!["A", "-->", "", "---", "B"]);
+    }
+
+    #[test]
+    fn test_re_split_keep_delimiters_at_ends() {
+        let parts = re_split_keep("-->", "-->A-->");
+        assert_eq!(parts, vec!["", "-->", "A", "-->", ""]);
+    }
+
+    #[test]
+    fn test_re_split_keep_no_match() {
+        assert_eq!(re_split_keep("-->", "plain"), vec!["plain"]);
+        assert_eq!(re_split_keep("-->", ""), vec![""]);
+    }
+
+    #[test]
+    fn test_re_split_keep_join_round_trip() {
+        let text = "x + y*(z - 1)";
+        assert_eq!(re_split_keep(r"[-+*()]", text).concat(), text);
+    }
+
+    // ── re_compile / pat_match / pat_is_match ────────────────
+    #[test]
+    fn test_pat_match_equivalent_to_re_match() {
+        let pattern = "[a-zA-Z_][a-zA-Z0-9_]*";
+        let p = re_compile(pattern);
+        let text = "node_A --> node_B; 42";
+        for pos in 0..=(text.len() as i32 + 1) {
+            assert_eq!(pat_match(p.clone(), text, pos), re_match(pattern, text, pos));
+        }
+    }
+
+    #[test]
+    fn test_pat_is_match_equivalent_to_re_is_match() {
+        let p = re_compile(r"\d+");
+        for text in ["x99y", "none", ""] {
+            assert_eq!(pat_is_match(p.clone(), text), re_is_match(r"\d+", text));
+        }
+    }
+
+    #[test]
+    fn test_pat_handle_clone_shares_regex() {
+        let p1 = re_compile("[a-z]+");
+        let p2 = p1.clone();
+        assert!(Rc::ptr_eq(&p1, &p2));
+        assert_eq!(pat_match(p2, "abc", 0), (true, "abc".to_string(), 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid regex pattern")]
+    fn test_re_compile_invalid_panics() {
+        re_compile("[a-z");
+    }
+
+    // ── re_try_match ─────────────────────────────────────────
+    #[test]
+    fn test_re_try_match_success() {
+        let (matched, m, end, err) = re_try_match("[a-z]+", "hello world", 6);
+        assert!(matched);
+        assert_eq!(m, "world");
+        assert_eq!(end, 11);
+        assert_eq!(err, "");
+    }
+
+    #[test]
+    fn test_re_try_match_no_match_has_no_error() {
+        let (matched, m, end, err) = re_try_match("[0-9]+", "hello", 0);
+        assert!(!matched);
+        assert_eq!(m, "");
+        assert_eq!(end, 0);
+        assert_eq!(err, "");
+    }
+
+    #[test]
+    fn test_re_try_match_invalid_pattern_reports_error() {
+        let (matched, _, end, err) = re_try_match("(oops", "oops", 0);
+        assert!(!matched);
+        assert_eq!(end, 0);
+        assert!(!err.is_empty());
+        assert!(REGEX_CACHE.with(|c| !c.borrow().map.contains_key("(oops")));
+    }
+
+    #[test]
+    fn test_re_try_match_bad_positions_do_not_panic() {
+        let (matched, _, end, err) = re_try_match("[a-z]+", "abc", 10);
+        assert!(!matched);
+        assert_eq!(end, 10);
+        assert_eq!(err, "re: pos 10 out of range 0..=3");
+        let (matched, _, _, err) = re_try_match("[a-z]+", "abc", -1);
+        assert!(!matched);
+        assert_eq!(err, "re: pos -1 out of range 0..=3");
+        let (matched, _, end, err) = re_try_match("[a-z]*", "abc", 3);
+        assert!(matched);
+        assert_eq!(end, 3);
+        assert_eq!(err, "");
+        let (matched, _, _, err) = re_try_match(".", "é", 1);
+        assert!(!matched);
+        assert!(!err.is_empty());
+    }
+
+    // ── anchored matching ────────────────────────────────────
+    #[test]
+    fn test_re_match_verbose_pattern_with_trailing_comment() {
+        let (matched, m, end) = re_match("(?x) a+ # comment", "aab", 0);
+        assert!(matched);
+        assert_eq!(m, "aa");
+        assert_eq!(end, 2);
+        let (matched, _, _, err) = re_try_match("(?x) [0-9]+ # digits", "x12", 1);
+        assert!(matched);
+        assert_eq!(err, "");
+        // Without (?x), '#' is literal and no newline is required after it.
+        let (matched, m, _) = re_match("a#b", "a#b\n", 0);
+        assert!(matched);
+        assert_eq!(m, "a#b");
+    }
+
+    #[test]
+    fn test_re_match_pathological_digits_every_position() {
+        // Unanchored find() would rescan the remaining digits at each of the
+        // positions (quadratic); anchored matching rejects each immediately.
+        let text = "0".repeat(200_000);
+        let start = std::time::Instant::now();
+        for pos in 0..text.len() as i32 {
+            let (matched, _, end) = re_match("[a-z]+", &text, pos);
+            assert!(!matched);
+            assert_eq!(end, pos);
+        }
+        assert!(start.elapsed().as_secs() < 10);
+    }
+
+    #[test]
+    fn test_re_match_alternation_prefers_first_branch() {
+        // Same leftmost-first result as the unanchored search.
+        assert_eq!(re_match("a|ab", "abc", 0), (true, "a".to_string(), 1));
+        assert_eq!(re_match("-->|--", "--> x", 0), (true, "-->".to_string(), 3));
+    }
+
+    #[test]
+    fn test_re_match_empty_match_at_pos() {
+        assert_eq!(re_match("[a-z]*", "12", 0), (true, String::new(), 0));
+    }
+
+    #[test]
+    fn test_re_match_pattern_with_own_anchor() {
+        // As before, `^` is relative to the slice starting at pos.
+        assert!(re_match("^abc", "abc", 0).0);
+        assert!(re_match("^abc", "xabc", 1).0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid regex pattern")]
+    fn test_re_match_wrap_does_not_validate_invalid_pattern() {
+        re_match(")(?:x", "x", 0);
+    }
 }
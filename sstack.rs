@@ -0,0 +1,213 @@
+// ============================================================
+// Homun Runtime — sstack.rs: Shared LIFO Stack
+// stdlib, no external crates required.
+//
+// Usage in .hom:
+//   use sstack
+//
+//   s := sstack_new()
+//   sstack_push(s, "root")
+//   ok, top := sstack_peek(s)     // (false, "") when empty
+//   ok, node := sstack_pop(s)
+//   n := sstack_len(s)
+//   empty := sstack_is_empty(s)
+//
+//   st := istack_new()            // same API over int payloads
+//   istack_push(st, 7)
+//
+// Implementation note:
+//   Same sharing model as heap.rs: Rc<RefCell<Vec<...>>> so that
+//   Homun's clone-based calling convention (every variable argument
+//   becomes arg.clone()) still refers to the SAME underlying stack.
+//   A helper .hom function that pushes onto the stack mutates the
+//   caller's stack, unlike the plain Vec helpers in std/stack.rs.
+//
+//   Lengths use i32 to match .hom's int type.  item accepts
+//   impl AsRef<str> so &str literals (Rust tests) and String values
+//   (homunc codegen) both work.
+// ============================================================
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// LIFO stack of String items shared through Rc<RefCell<...>>.
+pub type SStack = Rc<RefCell<Vec<String>>>;
+
+/// LIFO stack of i32 items shared through Rc<RefCell<...>>.
+pub type IStack = Rc<RefCell<Vec<i32>>>;
+
+/// Create a new empty stack.
+pub fn sstack_new() -> SStack {
+    Rc::new(RefCell::new(Vec::new()))
+}
+
+/// Push `item` onto the top of the stack.
+pub fn sstack_push(s: SStack, item: impl AsRef<str>) {
+    s.borrow_mut().push(item.as_ref().to_string());
+}
+
+/// Pop the top item. Returns `(false, "")` if the stack is empty.
+pub fn sstack_pop(s: SStack) -> (bool, String) {
+    match s.borrow_mut().pop() {
+        Some(x) => (true, x),
+        None => (false, String::new()),
+    }
+}
+
+/// Return the top item without removing it. `(false, "")` if empty.
+pub fn sstack_peek(s: SStack) -> (bool, String) {
+    match s.borrow().last() {
+        Some(x) => (true, x.clone()),
+        None => (false, String::new()),
+    }
+}
+
+/// Return the number of items on the stack (i32 for .hom int compatibility).
+pub fn sstack_len(s: SStack) -> i32 {
+    s.borrow().len() as i32
+}
+
+/// Return `true` if the stack contains no items.
+pub fn sstack_is_empty(s: SStack) -> bool {
+    s.borrow().is_empty()
+}
+
+/// Create a new empty int stack.
+pub fn istack_new() -> IStack {
+    Rc::new(RefCell::new(Vec::new()))
+}
+
+/// Push `item` onto the top of the int stack.
+pub fn istack_push(s: IStack, item: i32) {
+    s.borrow_mut().push(item);
+}
+
+/// Pop the top item. Returns `(false, 0)` if the stack is empty.
+pub fn istack_pop(s: IStack) -> (bool, i32) {
+    match s.borrow_mut().pop() {
+        Some(x) => (true, x),
+        None => (false, 0),
+    }
+}
+
+/// Return the top item without removing it. `(false, 0)` if empty.
+pub fn istack_peek(s: IStack) -> (bool, i32) {
+    match s.borrow().last() {
+        Some(x) => (true, *x),
+        None => (false, 0),
+    }
+}
+
+/// Return the number of items on the int stack.
+pub fn istack_len(s: IStack) -> i32 {
+    s.borrow().len() as i32
+}
+
+/// Return `true` if the int stack contains no items.
+pub fn istack_is_empty(s: IStack) -> bool {
+    s.borrow().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ── sstack_new ──────────────────────────────────────────
+    #[test]
+    fn test_sstack_new_is_empty() {
+        let s = sstack_new();
+        assert!(sstack_is_empty(s.clone()));
+        assert_eq!(sstack_len(s.clone()), 0);
+    }
+
+    // ── sstack_push / sstack_pop ────────────────────────────
+    #[test]
+    fn test_sstack_lifo_order() {
+        let s = sstack_new();
+        sstack_push(s.clone(), "a");
+        sstack_push(s.clone(), "b");
+        sstack_push(s.clone(), "c");
+        assert_eq!(sstack_pop(s.clone()), (true, "c".to_string()));
+        assert_eq!(sstack_pop(s.clone()), (true, "b".to_string()));
+        assert_eq!(sstack_pop(s.clone()), (true, "a".to_string()));
+        assert!(sstack_is_empty(s.clone()));
+    }
+
+    #[test]
+    fn test_sstack_pop_empty() {
+        let s = sstack_new();
+        assert_eq!(sstack_pop(s.clone()), (false, String::new()));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_sstack_push_string_type() {
+        let s = sstack_new();
+        let item = String::from("alpha");
+        sstack_push(s.clone(), item);
+        assert_eq!(sstack_len(s.clone()), 1);
+    }
+
+    // ── sstack_peek ─────────────────────────────────────────
+    #[test]
+    fn test_sstack_peek_does_not_consume() {
+        let s = sstack_new();
+        sstack_push(s.clone(), "x");
+        sstack_push(s.clone(), "y");
+        assert_eq!(sstack_peek(s.clone()), (true, "y".to_string()));
+        assert_eq!(sstack_len(s.clone()), 2);
+    }
+
+    #[test]
+    fn test_sstack_peek_empty() {
+        let s = sstack_new();
+        assert_eq!(sstack_peek(s.clone()), (false, String::new()));
+    }
+
+    // ── cross-function mutation (the reason this type exists) ─
+    #[test]
+    fn test_sstack_helper_push_visible_to_caller() {
+        fn push_children(s: SStack) {
+            sstack_push(s.clone(), "left");
+            sstack_push(s.clone(), "right");
+        }
+        let s = sstack_new();
+        push_children(s.clone());
+        assert_eq!(sstack_len(s.clone()), 2);
+        assert_eq!(sstack_pop(s.clone()), (true, "right".to_string()));
+    }
+
+    // ── Rc clone semantics: all handles share one stack ─────
+    #[test]
+    fn test_rc_clone_shares_state() {
+        let s1 = sstack_new();
+        let s2 = s1.clone();
+        sstack_push(s1.clone(), "seven");
+        assert_eq!(sstack_len(s2.clone()), 1);
+        assert_eq!(sstack_pop(s2.clone()), (true, "seven".to_string()));
+        assert!(sstack_is_empty(s1.clone()));
+    }
+
+    // ── istack ──────────────────────────────────────────────
+    #[test]
+    fn test_istack_lifo_order() {
+        let s = istack_new();
+        istack_push(s.clone(), 3);
+        istack_push(s.clone(), 1);
+        assert_eq!(istack_peek(s.clone()), (true, 1));
+        assert_eq!(istack_pop(s.clone()), (true, 1));
+        assert_eq!(istack_pop(s.clone()), (true, 3));
+        assert_eq!(istack_pop(s.clone()), (false, 0));
+        assert!(istack_is_empty(s.clone()));
+    }
+
+    #[test]
+    fn test_istack_rc_clone_shares_state() {
+        let s1 = istack_new();
+        let s2 = s1.clone();
+        istack_push(s1.clone(), 42);
+        assert_eq!(istack_len(s2.clone()), 1);
+        assert_eq!(istack_pop(s2.clone()), (true, 42));
+        assert!(istack_is_empty(s1.clone()));
+    }
+}
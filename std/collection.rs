@@ -8,6 +8,18 @@ pub fn sorted<T: Clone + Ord>(v: &[T]) -> Vec<T> {
     let mut out = v.to_vec(); out.sort(); out
 }
 
+pub fn is_sorted<T: PartialOrd + Clone>(v: &[T]) -> bool {
+    v.windows(2).all(|w| w[0] <= w[1])
+}
+
+pub fn is_sorted_desc<T: PartialOrd + Clone>(v: &[T]) -> bool {
+    v.windows(2).all(|w| w[0] >= w[1])
+}
+
+pub fn is_sorted_by_key<T: Clone, K: PartialOrd>(v: &[T], f: impl Fn(T) -> K) -> bool {
+    v.windows(2).all(|w| f(w[0].clone()) <= f(w[1].clone()))
+}
+
 pub fn reversed<T: Clone>(v: &[T]) -> Vec<T> {
     let mut out = v.to_vec(); out.reverse(); out
 }
@@ -24,16 +36,38 @@ pub fn flatten<T: Clone>(v: &[Vec<T>]) -> Vec<T> {
     v.iter().flat_map(|x| x.iter().cloned()).collect()
 }
 
+pub fn flatten2<T: Clone>(v: &[Vec<Vec<T>>]) -> Vec<T> {
+    v.iter().flat_map(|x| x.iter().flat_map(|y| y.iter().cloned())).collect()
+}
+
+pub fn flat_map<T: Clone, U>(v: &[T], f: impl Fn(T) -> Vec<U>) -> Vec<U> {
+    v.iter().cloned().flat_map(f).collect()
+}
+
 pub fn any<T: Clone>(v: &[T], f: impl Fn(T) -> bool) -> bool {
-    v.iter().cloned().any(|x| f(x))
+    v.iter().any(|x| f(x.clone()))
 }
 
 pub fn all<T: Clone>(v: &[T], f: impl Fn(T) -> bool) -> bool {
-    v.iter().cloned().all(|x| f(x))
+    v.iter().all(|x| f(x.clone()))
 }
 
 pub fn count<T: Clone>(v: &[T], f: impl Fn(T) -> bool) -> i32 {
-    v.iter().cloned().filter(|x| f(x.clone())).count() as i32
+    v.iter().filter(|x| f((*x).clone())).count() as i32
+}
+
+// _ref variants pass &T to the predicate and never clone elements.
+
+pub fn any_ref<T>(v: &[T], f: impl Fn(&T) -> bool) -> bool {
+    v.iter().any(f)
+}
+
+pub fn all_ref<T>(v: &[T], f: impl Fn(&T) -> bool) -> bool {
+    v.iter().all(f)
+}
+
+pub fn count_ref<T>(v: &[T], f: impl Fn(&T) -> bool) -> i32 {
+    v.iter().filter(|x| f(x)).count() as i32
 }
 
 pub fn unique<T: Clone + Eq + std::hash::Hash>(v: &[T]) -> Vec<T> {
@@ -45,6 +79,16 @@ pub fn index_of<T: Clone + PartialEq>(v: &[T], item: &T) -> i32 {
     v.iter().position(|x| x == item).map(|i| i as i32).unwrap_or(-1)
 }
 
+pub fn index_of_from<T: Clone + PartialEq>(v: &[T], item: &T, start: i32) -> i32 {
+    let len = v.len() as i32;
+    let s = if start < 0 { (len + start).max(0) } else { start.min(len) } as usize;
+    v[s..].iter().position(|x| x == item).map(|i| (s + i) as i32).unwrap_or(-1)
+}
+
+pub fn last_index_of<T: Clone + PartialEq>(v: &[T], item: &T) -> i32 {
+    v.iter().rposition(|x| x == item).map(|i| i as i32).unwrap_or(-1)
+}
+
 pub fn push<T>(v: &mut Vec<T>, item: T) {
     v.push(item);
 }
@@ -57,3 +101,652 @@ pub fn remove<T>(v: &mut Vec<T>, index: i32) -> T {
     let i = if index < 0 { (v.len() as i32 + index).max(0) as usize } else { index as usize };
     v.remove(i)
 }
+
+pub fn remove_item<T: PartialEq>(v: &mut Vec<T>, item: &T) -> bool {
+    match v.iter().position(|x| x == item) {
+        Some(i) => { v.remove(i); true }
+        None => false,
+    }
+}
+
+pub fn retain_where<T>(v: &mut Vec<T>, f: impl Fn(&T) -> bool) -> i32 {
+    let before = v.len();
+    v.retain(|x| f(x));
+    (before - v.len()) as i32
+}
+
+pub fn range_vec(start: i32, end: i32, step: i32) -> Vec<i32> {
+    let mut out = Vec::new();
+    let mut i = start;
+    while (step > 0 && i < end) || (step < 0 && i > end) {
+        out.push(i);
+        // Stop instead of overflowing when the range ends near i32::MAX/MIN.
+        match i.checked_add(step) {
+            Some(next) => i = next,
+            None => break,
+        }
+    }
+    out
+}
+
+pub fn linspace(start: f32, end: f32, n: i32) -> Vec<f32> {
+    if n <= 0 { return vec![]; }
+    if n == 1 { return vec![start]; }
+    let last = (n - 1) as f32;
+    let mut out: Vec<f32> = (0..n).map(|i| start + (end - start) * (i as f32 / last)).collect();
+    out[(n - 1) as usize] = end;
+    out
+}
+
+pub fn repeat_item<T: Clone>(item: T, n: i32) -> Vec<T> {
+    if n <= 0 { return vec![]; }
+    vec![item; n as usize]
+}
+
+pub fn fill_2d<T: Clone>(item: T, rows: i32, cols: i32) -> Vec<Vec<T>> {
+    if rows <= 0 { return vec![]; }
+    (0..rows).map(|_| repeat_item(item.clone(), cols)).collect()
+}
+
+pub fn cumsum_ints(v: &[i32]) -> Vec<i64> {
+    scan(v, 0i64, |acc, x| acc + x as i64)
+}
+
+pub fn cumsum_floats(v: &[f32]) -> Vec<f32> {
+    scan(v, 0.0f32, |acc, x| acc + x)
+}
+
+pub fn scan<T: Clone, A: Clone>(v: &[T], init: A, f: impl Fn(A, T) -> A) -> Vec<A> {
+    let mut acc = init;
+    let mut out = Vec::with_capacity(v.len());
+    for x in v.iter().cloned() {
+        acc = f(acc, x);
+        out.push(acc.clone());
+    }
+    out
+}
+
+// ── Seeded randomness ───────────────────────────────────────
+// shuffle/choice/sample use xorshift64* seeded through one splitmix64 step,
+// so the same seed gives the same result on every run and platform.
+// shuffle is a Fisher–Yates pass from the back: for i = len-1 down to 1,
+// swap v[i] with v[next_u64() % (i + 1)].  sample(k) runs the same swaps from
+// the front for k steps and returns the first k elements.
+
+pub struct HomunRng(u64);
+
+impl HomunRng {
+    pub fn new(seed: i64) -> Self {
+        let mut z = (seed as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        HomunRng(if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+pub fn shuffle<T: Clone>(v: &[T], seed: i64) -> Vec<T> {
+    let mut out = v.to_vec();
+    let mut rng = HomunRng::new(seed);
+    for i in (1..out.len()).rev() {
+        let j = rng.below(i + 1);
+        out.swap(i, j);
+    }
+    out
+}
+
+pub fn choice<T: Clone + Default>(v: &[T], seed: i64) -> (bool, T) {
+    if v.is_empty() { return (false, T::default()); }
+    let mut rng = HomunRng::new(seed);
+    (true, v[rng.below(v.len())].clone())
+}
+
+pub fn sample<T: Clone>(v: &[T], k: i32, seed: i64) -> Vec<T> {
+    let mut out = v.to_vec();
+    let k = (k.max(0) as usize).min(out.len());
+    let mut rng = HomunRng::new(seed);
+    for i in 0..k {
+        let j = i + rng.below(out.len() - i);
+        out.swap(i, j);
+    }
+    out.truncate(k);
+    out
+}
+
+// ── Order-preserving set operations on vecs ─────────────────
+
+pub fn intersect<T: Clone + Eq + std::hash::Hash>(a: &[T], b: &[T]) -> Vec<T> {
+    let other: HashSet<&T> = b.iter().collect();
+    let mut seen = HashSet::new();
+    a.iter().filter(|x| other.contains(x) && seen.insert(*x)).cloned().collect()
+}
+
+pub fn difference<T: Clone + Eq + std::hash::Hash>(a: &[T], b: &[T]) -> Vec<T> {
+    let other: HashSet<&T> = b.iter().collect();
+    let mut seen = HashSet::new();
+    a.iter().filter(|x| !other.contains(x) && seen.insert(*x)).cloned().collect()
+}
+
+pub fn union_vecs<T: Clone + Eq + std::hash::Hash>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut seen = HashSet::new();
+    a.iter().chain(b.iter()).filter(|x| seen.insert(*x)).cloned().collect()
+}
+
+pub fn interleave<T: Clone>(a: &[T], b: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    for i in 0..a.len().max(b.len()) {
+        if let Some(x) = a.get(i) { out.push(x.clone()); }
+        if let Some(x) = b.get(i) { out.push(x.clone()); }
+    }
+    out
+}
+
+pub fn round_robin<T: Clone>(vs: &[Vec<T>]) -> Vec<T> {
+    let longest = vs.iter().map(|v| v.len()).max().unwrap_or(0);
+    let mut out = Vec::with_capacity(vs.iter().map(|v| v.len()).sum());
+    for i in 0..longest {
+        for v in vs {
+            if let Some(x) = v.get(i) { out.push(x.clone()); }
+        }
+    }
+    out
+}
+
+pub fn vec_to_set<T: Clone + Eq + std::hash::Hash>(v: Vec<T>) -> HashSet<T> {
+    v.into_iter().collect()
+}
+
+pub fn set_to_sorted_vec<T: Clone + Ord>(s: &HashSet<T>) -> Vec<T> {
+    let mut out: Vec<T> = s.iter().cloned().collect(); out.sort(); out
+}
+
+pub fn set_to_vec_unsorted<T: Clone>(s: &HashSet<T>) -> Vec<T> {
+    s.iter().cloned().collect()
+}
+
+// ── Slice min/max ───────────────────────────────────────────
+// Float versions skip NaN; an all-NaN or empty slice yields NaN.
+// Int versions return 0 on an empty slice.  The try_ variants report
+// false only for an empty slice (an all-NaN slice gives (true, NaN)).
+
+pub fn try_min_float(v: &[f32]) -> (bool, f32) {
+    match v.iter().cloned().filter(|x| !x.is_nan()).reduce(f32::min) {
+        Some(m) => (true, m),
+        None => (!v.is_empty(), f32::NAN),
+    }
+}
+
+pub fn try_max_float(v: &[f32]) -> (bool, f32) {
+    match v.iter().cloned().filter(|x| !x.is_nan()).reduce(f32::max) {
+        Some(m) => (true, m),
+        None => (!v.is_empty(), f32::NAN),
+    }
+}
+
+pub fn min_float(v: &[f32]) -> f32 { try_min_float(v).1 }
+pub fn max_float(v: &[f32]) -> f32 { try_max_float(v).1 }
+
+pub fn try_min_int(v: &[i32]) -> (bool, i32) {
+    v.iter().min().map(|m| (true, *m)).unwrap_or((false, 0))
+}
+
+pub fn try_max_int(v: &[i32]) -> (bool, i32) {
+    v.iter().max().map(|m| (true, *m)).unwrap_or((false, 0))
+}
+
+pub fn min_int(v: &[i32]) -> i32 { try_min_int(v).1 }
+pub fn max_int(v: &[i32]) -> i32 { try_max_int(v).1 }
+
+#[cfg(test)]
+mod collection_tests {
+    use super::*;
+
+    // ── range_vec / linspace ────────────────────────────────
+    #[test]
+    fn test_range_vec_matches_range_macro() {
+        for (a, b, st) in [(0, 10, 1), (0, 10, 3), (2, 3, 5), (10, 0, -1), (10, -5, -4), (5, 5, 1), (0, 5, -1)] {
+            let expected: Vec<i32> = range!(a, b, st).collect();
+            assert_eq!(range_vec(a, b, st), expected, "range({}, {}, {})", a, b, st);
+        }
+    }
+
+    #[test]
+    fn test_range_vec_step_zero_is_empty() {
+        assert!(range_vec(0, 10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_range_vec_near_i32_limits() {
+        assert_eq!(range_vec(i32::MAX - 2, i32::MAX, 5), vec![i32::MAX - 2]);
+        assert_eq!(range_vec(i32::MAX - 3, i32::MAX, 2), vec![i32::MAX - 3, i32::MAX - 1]);
+        assert_eq!(range_vec(i32::MIN + 2, i32::MIN, -5), vec![i32::MIN + 2]);
+        assert_eq!(range_vec(i32::MIN + 1, i32::MIN, -1), vec![i32::MIN + 1]);
+    }
+
+    #[test]
+    fn test_linspace_endpoints_exact() {
+        let v = linspace(0.1, 0.7, 7);
+        assert_eq!(v.len(), 7);
+        assert_eq!(v[0], 0.1);
+        assert_eq!(v[6], 0.7);
+        assert_eq!(linspace(0.0, 1.0, 5), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_linspace_small_n() {
+        assert!(linspace(0.0, 1.0, 0).is_empty());
+        assert!(linspace(0.0, 1.0, -2).is_empty());
+        assert_eq!(linspace(3.0, 9.0, 1), vec![3.0]);
+        assert_eq!(linspace(3.0, 9.0, 2), vec![3.0, 9.0]);
+    }
+
+    // ── repeat_item / fill_2d ───────────────────────────────
+    #[test]
+    fn test_repeat_item() {
+        assert_eq!(repeat_item(7, 3), vec![7, 7, 7]);
+        assert!(repeat_item(7, 0).is_empty());
+        assert!(repeat_item(7, -1).is_empty());
+    }
+
+    #[test]
+    fn test_fill_2d_rows_are_independent() {
+        let mut grid = fill_2d(0, 2, 3);
+        assert_eq!(grid, vec![vec![0, 0, 0], vec![0, 0, 0]]);
+        grid[0][1] = 5;
+        assert_eq!(grid[1], vec![0, 0, 0]);
+        assert_eq!(fill_2d('x', 1, 1), vec![vec!['x']]);
+        assert!(fill_2d(0, 0, 3).is_empty());
+        assert!(fill_2d(0, -1, 3).is_empty());
+        assert_eq!(fill_2d(0, 1, -2), vec![Vec::<i32>::new()]);
+        assert_eq!(fill_2d(0, 2, 0), vec![Vec::<i32>::new(), Vec::new()]);
+    }
+
+    #[test]
+    fn test_repeat_item_string() {
+        let s = String::from("ab");
+        assert_eq!(repeat_item(s.clone(), 2), vec![s.clone(), s]);
+    }
+
+    // ── predicate cloning: by-value vs _ref ─────────────────
+    // Counts clones so the _ref paths can be checked to allocate nothing
+    // beyond the elements they keep.
+    #[derive(PartialEq, Debug)]
+    struct Counted(i32, std::rc::Rc<std::cell::Cell<usize>>);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.1.set(self.1.get() + 1);
+            Counted(self.0, self.1.clone())
+        }
+    }
+
+    fn counted(n: i32) -> (Vec<Counted>, std::rc::Rc<std::cell::Cell<usize>>) {
+        let clones = std::rc::Rc::new(std::cell::Cell::new(0));
+        ((0..n).map(|i| Counted(i, clones.clone())).collect(), clones)
+    }
+
+    #[test]
+    fn test_ref_predicates_never_clone() {
+        let (v, clones) = counted(1000);
+        assert_eq!(count_ref(&v, |x| x.0 % 2 == 0), 500);
+        assert!(any_ref(&v, |x| x.0 == 999));
+        assert!(all_ref(&v, |x| x.0 >= 0));
+        assert_eq!(clones.get(), 0);
+    }
+
+    #[test]
+    fn test_by_value_predicates_clone_once_per_visit() {
+        let (v, clones) = counted(1000);
+        assert_eq!(count(&v, |x: Counted| x.0 % 2 == 0), 500);
+        assert_eq!(clones.get(), 1000);
+        clones.set(0);
+        assert!(any(&v, |x: Counted| x.0 == 9));
+        assert_eq!(clones.get(), 10);
+        clones.set(0);
+        assert!(!all(&v, |x: Counted| x.0 < 4));
+        assert_eq!(clones.get(), 5);
+    }
+
+    #[test]
+    fn test_filter_macros_clone_counts() {
+        let (v, clones) = counted(1000);
+        let kept = filter_ref!(v, |x: &Counted| x.0 % 10 == 0);
+        assert_eq!(kept.len(), 100);
+        assert_eq!(clones.get(), 100);
+        clones.set(0);
+        let kept = filter!(v, |x: Counted| x.0 % 10 == 0);
+        assert_eq!(kept.len(), 100);
+        assert_eq!(clones.get(), 1100);
+    }
+
+    #[test]
+    fn test_by_value_and_ref_agree() {
+        let v = vec![3, -1, 4, -1, 5];
+        assert_eq!(count(&v, |x| x < 0), count_ref(&v, |x| *x < 0));
+        assert_eq!(any(&v, |x| x > 4), any_ref(&v, |x| *x > 4));
+        assert_eq!(all(&v, |x| x != 0), all_ref(&v, |x| *x != 0));
+        assert_eq!(filter!(v, |x| x > 0), filter_ref!(v, |x: &i32| *x > 0));
+    }
+
+    // ── index_of_from / last_index_of ───────────────────────
+    #[test]
+    fn test_index_of_from_start_position_itself() {
+        let v = vec![1, 2, 1, 2, 1];
+        assert_eq!(index_of_from(&v, &1, 0), 0);
+        assert_eq!(index_of_from(&v, &1, 2), 2);
+        assert_eq!(index_of_from(&v, &1, 3), 4);
+    }
+
+    #[test]
+    fn test_index_of_from_out_of_range() {
+        let v = vec![1, 2, 3];
+        assert_eq!(index_of_from(&v, &1, 3), -1);
+        assert_eq!(index_of_from(&v, &1, 10), -1);
+        assert_eq!(index_of_from(&v, &9, 0), -1);
+    }
+
+    #[test]
+    fn test_index_of_from_negative_start() {
+        let v = vec![5, 6, 5, 6];
+        assert_eq!(index_of_from(&v, &5, -2), 2);
+        assert_eq!(index_of_from(&v, &5, -1), -1);
+        assert_eq!(index_of_from(&v, &5, -100), 0);
+    }
+
+    #[test]
+    fn test_last_index_of() {
+        let v = vec![String::from("a"), String::from("b"), String::from("a")];
+        assert_eq!(last_index_of(&v, &String::from("a")), 2);
+        assert_eq!(last_index_of(&v, &String::from("b")), 1);
+        assert_eq!(last_index_of(&v, &String::from("z")), -1);
+        assert_eq!(last_index_of::<i32>(&[], &1), -1);
+    }
+
+    // ── scan / cumsum ───────────────────────────────────────
+    #[test]
+    fn test_cumsum_empty() {
+        assert!(cumsum_ints(&[]).is_empty());
+        assert!(cumsum_floats(&[]).is_empty());
+        assert!(scan(&[] as &[i32], 0, |a, x| a + x).is_empty());
+    }
+
+    #[test]
+    fn test_cumsum_negative() {
+        assert_eq!(cumsum_ints(&[3, -5, 2, -1]), vec![3, -2, 0, -1]);
+        assert_eq!(cumsum_floats(&[1.5, -0.5, -2.0]), vec![1.5, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_cumsum_ints_does_not_overflow() {
+        let v = vec![i32::MAX, i32::MAX, i32::MIN];
+        assert_eq!(cumsum_ints(&v), vec![i32::MAX as i64, 2 * i32::MAX as i64, i32::MAX as i64 - 1]);
+    }
+
+    #[test]
+    fn test_scan_excludes_init_and_keeps_length() {
+        let v = vec![String::from("a"), String::from("b"), String::from("c")];
+        let out = scan(&v, String::from(">"), |acc, x| acc + &x);
+        assert_eq!(out, vec![String::from(">a"), String::from(">ab"), String::from(">abc")]);
+        assert_eq!(scan(&[1, 2, 3], 1, |a, x| a * x).len(), 3);
+    }
+
+    // ── shuffle / choice / sample ───────────────────────────
+    fn sorted_copy(v: &[i32]) -> Vec<i32> {
+        let mut s = v.to_vec(); s.sort(); s
+    }
+
+    #[test]
+    fn test_shuffle_same_seed_same_output() {
+        let v: Vec<i32> = (0..50).collect();
+        assert_eq!(shuffle(&v, 42), shuffle(&v, 42));
+        assert_eq!(sample(&v, 10, 7), sample(&v, 10, 7));
+        assert_eq!(choice(&v, 3), choice(&v, 3));
+        assert_ne!(shuffle(&v, 1), shuffle(&v, 2));
+    }
+
+    #[test]
+    fn test_shuffle_is_permutation() {
+        let v: Vec<i32> = (0..50).collect();
+        for seed in [0, 1, -1, 42, i64::MAX, i64::MIN] {
+            let s = shuffle(&v, seed);
+            assert_eq!(sorted_copy(&s), v, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_seed_zero_still_shuffles() {
+        let v: Vec<i32> = (0..20).collect();
+        assert_ne!(shuffle(&v, 0), v);
+        let mut rng = HomunRng::new(0);
+        assert_ne!(rng.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn test_sample_clamps_k() {
+        let v: Vec<i32> = (0..10).collect();
+        assert!(sample(&v, 0, 5).is_empty());
+        assert!(sample(&v, -3, 5).is_empty());
+        assert_eq!(sorted_copy(&sample(&v, 99, 5)), v);
+    }
+
+    #[test]
+    fn test_sample_is_without_replacement() {
+        let v: Vec<i32> = (0..30).collect();
+        let s = sample(&v, 12, 9);
+        assert_eq!(s.len(), 12);
+        let distinct: HashSet<i32> = s.iter().cloned().collect();
+        assert_eq!(distinct.len(), 12);
+        assert!(s.iter().all(|x| v.contains(x)));
+    }
+
+    #[test]
+    fn test_rng_empty_input() {
+        let empty: Vec<String> = vec![];
+        assert!(shuffle(&empty, 1).is_empty());
+        assert!(sample(&empty, 3, 1).is_empty());
+        assert_eq!(choice(&empty, 1), (false, String::new()));
+        let one = vec![String::from("only")];
+        assert_eq!(choice(&one, 123), (true, String::from("only")));
+        assert_eq!(shuffle(&one, 123), one);
+    }
+
+    // ── intersect / difference / union_vecs ─────────────────
+    #[test]
+    fn test_set_ops_with_duplicates() {
+        let a = vec![3, 1, 3, 2, 1];
+        let b = vec![1, 4, 3, 4];
+        assert_eq!(intersect(&a, &b), vec![3, 1]);
+        assert_eq!(difference(&a, &b), vec![2]);
+        assert_eq!(union_vecs(&a, &b), vec![3, 1, 2, 4]);
+    }
+
+    #[test]
+    fn test_set_ops_disjoint() {
+        let a = vec![String::from("x"), String::from("y")];
+        let b = vec![String::from("z")];
+        assert!(intersect(&a, &b).is_empty());
+        assert_eq!(difference(&a, &b), a);
+        assert_eq!(union_vecs(&a, &b), vec![String::from("x"), String::from("y"), String::from("z")]);
+    }
+
+    #[test]
+    fn test_set_ops_identical() {
+        let a = vec![2, 2, 1];
+        assert_eq!(intersect(&a, &a), vec![2, 1]);
+        assert!(difference(&a, &a).is_empty());
+        assert_eq!(union_vecs(&a, &a), vec![2, 1]);
+        assert!(union_vecs::<i32>(&[], &[]).is_empty());
+    }
+
+    // ── flat_map / flatten2 ─────────────────────────────────
+    #[test]
+    fn test_flat_map_with_empty_results() {
+        let v = vec![0, 1, 2, 3];
+        assert_eq!(flat_map(&v, |n| vec![n; n as usize]), vec![1, 2, 2, 3, 3, 3]);
+        assert!(flat_map(&v, |_| Vec::<i32>::new()).is_empty());
+        assert!(flat_map(&[] as &[i32], |n| vec![n]).is_empty());
+    }
+
+    #[test]
+    fn test_flatten2_depth_first_order() {
+        let v = vec![vec![vec![1, 2], vec![]], vec![], vec![vec![3], vec![4, 5]]];
+        assert_eq!(flatten2(&v), vec![1, 2, 3, 4, 5]);
+        assert!(flatten2::<i32>(&[]).is_empty());
+    }
+
+    // ── is_sorted ───────────────────────────────────────────
+    #[test]
+    fn test_is_sorted_trivial() {
+        assert!(is_sorted::<i32>(&[]));
+        assert!(is_sorted_desc::<i32>(&[]));
+        assert!(is_sorted(&[7]));
+        assert!(is_sorted_desc(&[7]));
+    }
+
+    #[test]
+    fn test_is_sorted_equal_runs() {
+        assert!(is_sorted(&[1, 1, 2, 2, 2, 3]));
+        assert!(is_sorted_desc(&[3, 3, 1, 1]));
+        assert!(is_sorted(&[4, 4]) && is_sorted_desc(&[4, 4]));
+    }
+
+    #[test]
+    fn test_is_sorted_last_pair_out_of_order() {
+        assert!(!is_sorted(&[1, 2, 3, 5, 4]));
+        assert!(!is_sorted_desc(&[5, 4, 3, 1, 2]));
+        assert!(!is_sorted(&[0.1, 0.2, 0.15]));
+    }
+
+    #[test]
+    fn test_is_sorted_by_key() {
+        let v = vec![String::from("b"), String::from("aa"), String::from("ccc")];
+        assert!(is_sorted_by_key(&v, |s: String| s.len()));
+        assert!(!is_sorted_by_key(&v, |s: String| s));
+    }
+
+    // ── remove_item / retain_where ──────────────────────────
+    #[test]
+    fn test_remove_item_absent() {
+        let mut v = vec![1, 2, 3];
+        assert!(!remove_item(&mut v, &9));
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_remove_item_only_first() {
+        let mut v = vec![String::from("a"), String::from("b"), String::from("a")];
+        assert!(remove_item(&mut v, &String::from("a")));
+        assert_eq!(v, vec![String::from("b"), String::from("a")]);
+    }
+
+    #[test]
+    fn test_retain_where_counts_removed() {
+        let mut v = vec![1, 2, 3, 4, 5];
+        assert_eq!(retain_where(&mut v, |x| x % 2 == 1), 2);
+        assert_eq!(v, vec![1, 3, 5]);
+        assert_eq!(retain_where(&mut v, |_| false), 3);
+        assert!(v.is_empty());
+        assert_eq!(retain_where(&mut v, |_| false), 0);
+    }
+
+    // ── interleave / round_robin ────────────────────────────
+    #[test]
+    fn test_interleave_unequal_lengths() {
+        assert_eq!(interleave(&[1, 3, 5, 7, 8], &[2, 4]), vec![1, 2, 3, 4, 5, 7, 8]);
+        assert_eq!(interleave(&[1], &[2, 3, 4]), vec![1, 2, 3, 4]);
+        assert_eq!(interleave(&[], &[2, 3]), vec![2, 3]);
+        assert!(interleave::<i32>(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_round_robin_order_and_length() {
+        let vs = vec![vec![1, 4, 7, 9], vec![], vec![2, 5], vec![3, 6, 8]];
+        let out = round_robin(&vs);
+        assert_eq!(out, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(out.len(), vs.iter().map(|v| v.len()).sum::<usize>());
+    }
+
+    #[test]
+    fn test_round_robin_empty() {
+        assert!(round_robin::<i32>(&[]).is_empty());
+        assert!(round_robin::<i32>(&[vec![], vec![]]).is_empty());
+        assert_eq!(round_robin(&[vec![String::from("x")]]), vec![String::from("x")]);
+    }
+
+    // ── vec_to_set / set_to_sorted_vec ──────────────────────
+    #[test]
+    fn test_vec_to_set_collapses_duplicates() {
+        let s = vec_to_set(vec![3, 1, 3, 2, 1]);
+        assert_eq!(s.len(), 3);
+        assert!(s.contains(&1) && s.contains(&2) && s.contains(&3));
+        assert!(vec_to_set(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_set_to_sorted_vec() {
+        let s = vec_to_set(vec![String::from("pear"), String::from("apple"), String::from("fig"), String::from("apple")]);
+        assert_eq!(set_to_sorted_vec(&s), vec![String::from("apple"), String::from("fig"), String::from("pear")]);
+        assert!(set_to_sorted_vec(&HashSet::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_set_to_vec_unsorted_has_every_item() {
+        let s = vec_to_set(vec![5, 4, 5]);
+        let mut v = set_to_vec_unsorted(&s);
+        v.sort();
+        assert_eq!(v, vec![4, 5]);
+        assert!(set_to_vec_unsorted(&HashSet::<String>::new()).is_empty());
+    }
+
+    // ── NaN-safe min/max ────────────────────────────────────
+    #[test]
+    fn test_min_max_float_skip_nan_in_middle() {
+        let v = vec![2.0, f32::NAN, -1.5, 4.0];
+        assert_eq!(try_min_float(&v), (true, -1.5));
+        assert_eq!(try_max_float(&v), (true, 4.0));
+        assert_eq!(min_float(&[f32::NAN, 3.0]), 3.0);
+        assert_eq!(max_float(&[3.0, f32::NAN]), 3.0);
+    }
+
+    #[test]
+    fn test_min_max_float_all_nan() {
+        let v = vec![f32::NAN, f32::NAN];
+        let (ok, m) = try_min_float(&v);
+        assert!(ok && m.is_nan());
+        let (ok, m) = try_max_float(&v);
+        assert!(ok && m.is_nan());
+        assert!(min_float(&v).is_nan());
+    }
+
+    #[test]
+    fn test_min_max_negatives() {
+        assert_eq!(try_min_float(&[-3.0, -7.25, -0.5]), (true, -7.25));
+        assert_eq!(try_max_float(&[-3.0, -7.25, -0.5]), (true, -0.5));
+        assert_eq!(try_min_int(&[-3, i32::MIN, 5]), (true, i32::MIN));
+        assert_eq!(try_max_int(&[-3, -9, -4]), (true, -3));
+        assert_eq!(min_int(&[4, -2]), -2);
+        assert_eq!(max_int(&[4, -2]), 4);
+    }
+
+    #[test]
+    fn test_min_max_empty() {
+        let (ok, m) = try_min_float(&[]);
+        assert!(!ok && m.is_nan());
+        let (ok, m) = try_max_float(&[]);
+        assert!(!ok && m.is_nan());
+        assert_eq!(try_min_int(&[]), (false, 0));
+        assert_eq!(try_max_int(&[]), (false, 0));
+        assert_eq!(min_int(&[]), 0);
+    }
+}
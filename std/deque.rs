@@ -2,7 +2,7 @@
 // Homun Deque Library — included by ext.rs
 // ============================================================
 
-use std::collections::VecDeque;
+// VecDeque is already imported by builtin.rs
 
 pub fn deque_new<T>() -> VecDeque<T> {
     VecDeque::new()
@@ -27,3 +27,340 @@ pub fn deque_pop_back<T>(d: &mut VecDeque<T>) -> Option<T> {
 pub fn deque_is_empty<T>(d: &VecDeque<T>) -> bool {
     d.is_empty()
 }
+
+pub fn deque_len<T>(d: &VecDeque<T>) -> i32 {
+    d.len() as i32
+}
+
+pub fn deque_peek_front<T: Clone>(d: &VecDeque<T>) -> Option<T> {
+    d.front().cloned()
+}
+
+pub fn deque_peek_back<T: Clone>(d: &VecDeque<T>) -> Option<T> {
+    d.back().cloned()
+}
+
+pub fn deque_try_peek_front<T: Clone + Default>(d: &VecDeque<T>) -> (bool, T) {
+    match d.front() {
+        Some(x) => (true, x.clone()),
+        None => (false, T::default()),
+    }
+}
+
+pub fn deque_try_peek_back<T: Clone + Default>(d: &VecDeque<T>) -> (bool, T) {
+    match d.back() {
+        Some(x) => (true, x.clone()),
+        None => (false, T::default()),
+    }
+}
+
+// ── Ring buffer: bounded deque of the last N items ──────────
+// Shared through Rc<RefCell<...>> like heap.rs's Heap, so .hom's
+// clone-based calls all push into the same buffer.
+
+pub struct RingBuf {
+    pub items: VecDeque<String>,
+    pub capacity: usize,
+}
+
+pub type Ring = std::rc::Rc<std::cell::RefCell<RingBuf>>;
+
+pub fn ring_new(capacity: i32) -> Ring {
+    let capacity = capacity.max(0) as usize;
+    std::rc::Rc::new(std::cell::RefCell::new(RingBuf { items: VecDeque::with_capacity(capacity), capacity }))
+}
+
+// Evicts the oldest item when full; with capacity 0 every push is a no-op.
+pub fn ring_push(r: Ring, item: impl AsRef<str>) {
+    let mut rb = r.borrow_mut();
+    if rb.capacity == 0 { return; }
+    if rb.items.len() == rb.capacity { rb.items.pop_front(); }
+    rb.items.push_back(item.as_ref().to_string());
+}
+
+pub fn ring_to_vec(r: Ring) -> Vec<String> {
+    r.borrow().items.iter().cloned().collect()
+}
+
+pub fn ring_len(r: Ring) -> i32 {
+    r.borrow().items.len() as i32
+}
+
+pub fn ring_is_full(r: Ring) -> bool {
+    let rb = r.borrow();
+    rb.items.len() == rb.capacity
+}
+
+pub fn deque_contains<T: PartialEq>(d: &VecDeque<T>, item: &T) -> bool {
+    d.contains(item)
+}
+
+pub fn deque_index_of<T: PartialEq>(d: &VecDeque<T>, item: &T) -> i32 {
+    d.iter().position(|x| x == item).map(|i| i as i32).unwrap_or(-1)
+}
+
+pub fn deque_clear<T>(d: &mut VecDeque<T>) {
+    d.clear();
+}
+
+pub fn deque_drain<T>(d: &mut VecDeque<T>) -> Vec<T> {
+    d.drain(..).collect()
+}
+
+pub fn deque_insert_at<T>(d: &mut VecDeque<T>, index: i32, item: T) {
+    let len = d.len() as i32;
+    let i = if index < 0 { len + index } else { index }.clamp(0, len) as usize;
+    d.insert(i, item);
+}
+
+pub fn deque_remove_at<T>(d: &mut VecDeque<T>, index: i32) -> Option<T> {
+    let i = if index < 0 { d.len() as i32 + index } else { index };
+    if i < 0 { return None; }
+    d.remove(i as usize)
+}
+
+pub fn deque_try_remove_at<T: Default>(d: &mut VecDeque<T>, index: i32) -> (bool, T) {
+    match deque_remove_at(d, index) {
+        Some(x) => (true, x),
+        None => (false, T::default()),
+    }
+}
+
+pub fn deque_move_to_front<T: PartialEq>(d: &mut VecDeque<T>, item: &T) -> bool {
+    match d.iter().position(|x| x == item) {
+        Some(i) => {
+            let x = d.remove(i).unwrap();
+            d.push_front(x);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn deque_swap<T>(d: &mut VecDeque<T>, i: i32, j: i32) {
+    let len = d.len() as i32;
+    let norm = |k: i32| -> usize {
+        let k2 = if k < 0 { len + k } else { k };
+        if k2 < 0 || k2 >= len {
+            panic!("deque_swap: index {} out of range for deque of length {}", k, len);
+        }
+        k2 as usize
+    };
+    d.swap(norm(i), norm(j));
+}
+
+pub fn deque_map<T: Clone, U>(d: &VecDeque<T>, f: impl Fn(T) -> U) -> VecDeque<U> {
+    d.iter().cloned().map(f).collect()
+}
+
+pub fn deque_filter<T: Clone>(d: &VecDeque<T>, f: impl Fn(T) -> bool) -> VecDeque<T> {
+    d.iter().filter(|x| f((*x).clone())).cloned().collect()
+}
+
+#[cfg(test)]
+mod deque_tests {
+    use super::*;
+
+    // Built with interleaved push_front/push_back so the logical order
+    // wraps around the end of the underlying buffer.
+    fn wrapped() -> VecDeque<i32> {
+        let mut d = VecDeque::with_capacity(4);
+        deque_push_back(&mut d, 2);
+        deque_push_front(&mut d, 1);
+        deque_push_back(&mut d, 3);
+        deque_push_front(&mut d, 0);
+        d
+    }
+
+    // ── len / peek ──────────────────────────────────────────
+    #[test]
+    fn test_deque_peek_empty() {
+        let d: VecDeque<String> = deque_new();
+        assert_eq!(deque_len(&d), 0);
+        assert_eq!(deque_peek_front(&d), None);
+        assert_eq!(deque_peek_back(&d), None);
+        assert_eq!(deque_try_peek_front(&d), (false, String::new()));
+        assert_eq!(deque_try_peek_back(&d), (false, String::new()));
+    }
+
+    #[test]
+    fn test_deque_peek_keeps_length() {
+        let d = wrapped();
+        assert_eq!(deque_peek_front(&d), Some(0));
+        assert_eq!(deque_peek_back(&d), Some(3));
+        assert_eq!(deque_try_peek_front(&d), (true, 0));
+        assert_eq!(deque_try_peek_back(&d), (true, 3));
+        assert_eq!(deque_len(&d), 4);
+        assert_eq!(len!(d), 4);
+    }
+
+    // ── ring buffer ─────────────────────────────────────────
+    #[test]
+    fn test_ring_push_exactly_capacity() {
+        let r = ring_new(3);
+        for s in ["a", "b", "c"] { ring_push(r.clone(), s); }
+        assert!(ring_is_full(r.clone()));
+        assert_eq!(ring_len(r.clone()), 3);
+        assert_eq!(ring_to_vec(r.clone()), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_ring_push_past_capacity_evicts_oldest() {
+        let r = ring_new(3);
+        for s in ["a", "b", "c", "d"] { ring_push(r.clone(), s); }
+        assert_eq!(ring_len(r.clone()), 3);
+        assert_eq!(ring_to_vec(r.clone()), vec!["b", "c", "d"]);
+        ring_push(r.clone(), String::from("e"));
+        assert_eq!(ring_to_vec(r.clone()), vec!["c", "d", "e"]);
+    }
+
+    #[test]
+    fn test_ring_capacity_zero_is_noop() {
+        for cap in [0, -4] {
+            let r = ring_new(cap);
+            ring_push(r.clone(), "x");
+            assert_eq!(ring_len(r.clone()), 0);
+            assert!(ring_to_vec(r.clone()).is_empty());
+            assert!(ring_is_full(r.clone()));
+        }
+    }
+
+    #[test]
+    fn test_ring_rc_clone_shares_state() {
+        let r1 = ring_new(2);
+        let r2 = r1.clone();
+        ring_push(r1.clone(), "one");
+        ring_push(r2.clone(), "two");
+        ring_push(r1.clone(), "three");
+        assert_eq!(ring_to_vec(r2.clone()), vec!["two", "three"]);
+        assert_eq!(ring_len(r1.clone()), 2);
+        assert!(ring_is_full(r2.clone()));
+    }
+
+    // ── contains / index_of ─────────────────────────────────
+    #[test]
+    fn test_deque_index_of_logical_positions() {
+        let d = wrapped();
+        assert_eq!(deque_index_of(&d, &0), 0);
+        assert_eq!(deque_index_of(&d, &2), 2);
+        assert_eq!(deque_index_of(&d, &3), 3);
+        assert_eq!(deque_index_of(&d, &9), -1);
+        assert!(deque_contains(&d, &1));
+        assert!(!deque_contains(&d, &9));
+    }
+
+    #[test]
+    fn test_deque_in_operator() {
+        let mut d: VecDeque<String> = deque_new();
+        deque_push_back(&mut d, String::from("b"));
+        deque_push_front(&mut d, String::from("a"));
+        assert!(homun_in!("a", d));
+        assert!(homun_in!(String::from("b"), d));
+        assert!(!homun_in!("c", d));
+        assert!(homun_in!(3, wrapped()));
+    }
+
+    // ── clear / drain ───────────────────────────────────────
+    #[test]
+    fn test_deque_drain_front_to_back() {
+        let mut d = wrapped();
+        assert_eq!(deque_drain(&mut d), vec![0, 1, 2, 3]);
+        assert!(deque_is_empty(&d));
+        assert!(deque_drain(&mut d).is_empty());
+    }
+
+    #[test]
+    fn test_deque_reusable_after_clear() {
+        let mut d = wrapped();
+        deque_clear(&mut d);
+        assert_eq!(deque_len(&d), 0);
+        deque_push_back(&mut d, 7);
+        deque_push_front(&mut d, 6);
+        assert_eq!(deque_pop_back(&mut d), Some(7));
+        assert_eq!(deque_pop_front(&mut d), Some(6));
+        assert_eq!(deque_pop_front(&mut d), None);
+    }
+
+    // ── insert_at / remove_at ───────────────────────────────
+    #[test]
+    fn test_deque_insert_at_ends_and_clamping() {
+        let mut d = wrapped();
+        deque_insert_at(&mut d, 0, 10);
+        deque_insert_at(&mut d, 5, 20);
+        assert_eq!(deque_drain(&mut d.clone()), vec![10, 0, 1, 2, 3, 20]);
+        deque_insert_at(&mut d, 100, 30);
+        deque_insert_at(&mut d, -100, 40);
+        assert_eq!(deque_peek_back(&d), Some(30));
+        assert_eq!(deque_peek_front(&d), Some(40));
+    }
+
+    #[test]
+    fn test_deque_insert_at_negative() {
+        let mut d = wrapped();
+        deque_insert_at(&mut d, -1, 9);
+        assert_eq!(deque_drain(&mut d), vec![0, 1, 2, 9, 3]);
+    }
+
+    #[test]
+    fn test_deque_remove_at() {
+        let mut d = wrapped();
+        assert_eq!(deque_remove_at(&mut d, 0), Some(0));
+        assert_eq!(deque_remove_at(&mut d, -1), Some(3));
+        assert_eq!(deque_try_remove_at(&mut d, 1), (true, 2));
+        assert_eq!(deque_drain(&mut d), vec![1]);
+    }
+
+    #[test]
+    fn test_deque_remove_at_out_of_range() {
+        let mut d = wrapped();
+        assert_eq!(deque_remove_at(&mut d, 4), None);
+        assert_eq!(deque_remove_at(&mut d, -5), None);
+        assert_eq!(deque_try_remove_at(&mut d, 99), (false, 0));
+        assert_eq!(deque_len(&d), 4);
+    }
+
+    // ── move_to_front / swap ────────────────────────────────
+    #[test]
+    fn test_deque_move_to_front() {
+        let mut d = wrapped();
+        assert!(deque_move_to_front(&mut d, &2));
+        assert_eq!(deque_drain(&mut d.clone()), vec![2, 0, 1, 3]);
+        assert!(deque_move_to_front(&mut d, &2));
+        assert_eq!(deque_drain(&mut d.clone()), vec![2, 0, 1, 3]);
+        assert!(!deque_move_to_front(&mut d, &9));
+        assert_eq!(deque_len(&d), 4);
+    }
+
+    #[test]
+    fn test_deque_swap_across_wrap_point() {
+        let mut d = wrapped();
+        deque_swap(&mut d, 0, 3);
+        assert_eq!(deque_drain(&mut d.clone()), vec![3, 1, 2, 0]);
+        deque_swap(&mut d, 1, -2);
+        assert_eq!(deque_drain(&mut d), vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "deque_swap: index 4 out of range for deque of length 4")]
+    fn test_deque_swap_out_of_range_panics() {
+        let mut d = wrapped();
+        deque_swap(&mut d, 0, 4);
+    }
+
+    // ── map / filter ────────────────────────────────────────
+    #[test]
+    fn test_deque_map_filter_empty() {
+        let d: VecDeque<i32> = deque_new();
+        assert!(deque_map(&d, |x| x * 2).is_empty());
+        assert!(deque_filter(&d, |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_deque_map_filter_keep_order_after_wrap() {
+        let d = wrapped();
+        let m = deque_map(&d, |x| format!("n{}", x));
+        assert_eq!(m, VecDeque::from(vec![String::from("n0"), String::from("n1"), String::from("n2"), String::from("n3")]));
+        assert_eq!(deque_filter(&d, |x| x != 1), VecDeque::from(vec![0, 2, 3]));
+        assert_eq!(deque_len(&d), 4);
+    }
+}
@@ -21,3 +21,714 @@ pub fn insert<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, V
 pub fn remove_key<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, V>, key: &K) -> Option<V> {
     d.remove(key)
 }
+
+pub fn get_or<K: Eq + std::hash::Hash, V: Clone>(d: &std::collections::HashMap<K, V>, key: &K, default: V) -> V {
+    d.get(key).cloned().unwrap_or(default)
+}
+
+pub fn get_or_str<V: Clone>(d: &std::collections::HashMap<String, V>, key: impl AsRef<str>, default: V) -> V {
+    d.get(key.as_ref()).cloned().unwrap_or(default)
+}
+
+pub fn try_get<K: Eq + std::hash::Hash, V: Clone + Default>(d: &std::collections::HashMap<K, V>, key: &K) -> (bool, V) {
+    match d.get(key) {
+        Some(v) => (true, v.clone()),
+        None => (false, V::default()),
+    }
+}
+
+pub fn setdefault<K: Eq + std::hash::Hash + Clone, V: Clone>(d: &mut std::collections::HashMap<K, V>, key: K, default: V) -> V {
+    d.entry(key).or_insert(default).clone()
+}
+
+pub fn append_to<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, Vec<V>>, key: K, item: V) {
+    d.entry(key).or_default().push(item);
+}
+
+pub fn merge<K: Eq + std::hash::Hash + Clone, V: Clone>(a: std::collections::HashMap<K, V>, b: std::collections::HashMap<K, V>) -> std::collections::HashMap<K, V> {
+    let mut out = a;
+    out.extend(b);
+    out
+}
+
+pub fn update<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, V>, other: std::collections::HashMap<K, V>) {
+    d.extend(other);
+}
+
+pub fn merge_with<K: Eq + std::hash::Hash + Clone, V: Clone>(a: std::collections::HashMap<K, V>, b: std::collections::HashMap<K, V>, f: impl Fn(V, V) -> V) -> std::collections::HashMap<K, V> {
+    let mut out = a;
+    for (k, v) in b {
+        let merged = match out.remove(&k) {
+            Some(old) => f(old, v),
+            None => v,
+        };
+        out.insert(k, merged);
+    }
+    out
+}
+
+pub fn map_values<K: Clone + Eq + std::hash::Hash, V: Clone, U>(d: &std::collections::HashMap<K, V>, f: impl Fn(V) -> U) -> std::collections::HashMap<K, U> {
+    d.iter().map(|(k, v)| (k.clone(), f(v.clone()))).collect()
+}
+
+// If f maps two keys to the same new key, which value survives is unspecified
+// (it follows HashMap iteration order).
+pub fn map_keys<K: Clone, V: Clone, J: Eq + std::hash::Hash>(d: &std::collections::HashMap<K, V>, f: impl Fn(K) -> J) -> std::collections::HashMap<J, V> {
+    d.iter().map(|(k, v)| (f(k.clone()), v.clone())).collect()
+}
+
+pub fn filter_entries<K: Clone + Eq + std::hash::Hash, V: Clone>(d: &std::collections::HashMap<K, V>, f: impl Fn(K, V) -> bool) -> std::collections::HashMap<K, V> {
+    d.iter()
+        .filter(|(k, v)| f((*k).clone(), (*v).clone()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+pub fn sorted_keys<K: Clone + Ord, V>(d: &std::collections::HashMap<K, V>) -> Vec<K> {
+    let mut out: Vec<K> = d.keys().cloned().collect(); out.sort(); out
+}
+
+pub fn sorted_keys_desc<K: Clone + Ord, V>(d: &std::collections::HashMap<K, V>) -> Vec<K> {
+    let mut out = sorted_keys(d); out.reverse(); out
+}
+
+pub fn sorted_entries<K: Clone + Ord, V: Clone>(d: &std::collections::HashMap<K, V>) -> Vec<(K, V)> {
+    let mut out = entries(d); out.sort_by(|a, b| a.0.cmp(&b.0)); out
+}
+
+pub fn sorted_entries_desc<K: Clone + Ord, V: Clone>(d: &std::collections::HashMap<K, V>) -> Vec<(K, V)> {
+    let mut out = entries(d); out.sort_by(|a, b| b.0.cmp(&a.0)); out
+}
+
+// Ties on value are broken by ascending key in both directions.
+
+pub fn entries_by_value<K: Clone + Ord, V: Clone + Ord>(d: &std::collections::HashMap<K, V>) -> Vec<(K, V)> {
+    let mut out = entries(d); out.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0))); out
+}
+
+pub fn entries_by_value_desc<K: Clone + Ord, V: Clone + Ord>(d: &std::collections::HashMap<K, V>) -> Vec<(K, V)> {
+    let mut out = entries(d); out.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))); out
+}
+
+// Counters: incr_by never removes an entry, so a count driven to 0 stays as 0.
+
+pub fn incr<K: Eq + std::hash::Hash + Clone>(d: &mut std::collections::HashMap<K, i32>, key: K) {
+    incr_by(d, key, 1);
+}
+
+pub fn decr<K: Eq + std::hash::Hash + Clone>(d: &mut std::collections::HashMap<K, i32>, key: K) {
+    incr_by(d, key, -1);
+}
+
+pub fn incr_by<K: Eq + std::hash::Hash + Clone>(d: &mut std::collections::HashMap<K, i32>, key: K, amount: i32) {
+    *d.entry(key).or_insert(0) += amount;
+}
+
+pub fn counter_from<T: Clone + Eq + std::hash::Hash>(v: &[T]) -> std::collections::HashMap<T, i32> {
+    let mut out = std::collections::HashMap::new();
+    for x in v { incr(&mut out, x.clone()); }
+    out
+}
+
+pub fn counter_total<K>(d: &std::collections::HashMap<K, i32>) -> i64 {
+    d.values().map(|&n| n as i64).sum()
+}
+
+pub fn pop_or<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, V>, key: &K, default: V) -> V {
+    d.remove(key).unwrap_or(default)
+}
+
+pub fn try_pop<K: Eq + std::hash::Hash, V: Default>(d: &mut std::collections::HashMap<K, V>, key: &K) -> (bool, V) {
+    match d.remove(key) {
+        Some(v) => (true, v),
+        None => (false, V::default()),
+    }
+}
+
+pub fn pop_any<K: Eq + std::hash::Hash + Clone + Default, V: Default>(d: &mut std::collections::HashMap<K, V>) -> (bool, K, V) {
+    let key = match d.keys().next() {
+        Some(k) => k.clone(),
+        None => return (false, K::default(), V::default()),
+    };
+    let val = d.remove(&key).unwrap();
+    (true, key, val)
+}
+
+// ── Nested paths: cfg["layout"]["direction"] without panicking ──
+
+pub fn get_path2<V: Clone + Default>(d: &std::collections::HashMap<String, std::collections::HashMap<String, V>>, k1: impl AsRef<str>, k2: impl AsRef<str>) -> (bool, V) {
+    match d.get(k1.as_ref()).and_then(|m| m.get(k2.as_ref())) {
+        Some(v) => (true, v.clone()),
+        None => (false, V::default()),
+    }
+}
+
+pub fn get_path3<V: Clone + Default>(d: &std::collections::HashMap<String, std::collections::HashMap<String, std::collections::HashMap<String, V>>>, k1: impl AsRef<str>, k2: impl AsRef<str>, k3: impl AsRef<str>) -> (bool, V) {
+    match d.get(k1.as_ref()).and_then(|m| m.get(k2.as_ref())).and_then(|m| m.get(k3.as_ref())) {
+        Some(v) => (true, v.clone()),
+        None => (false, V::default()),
+    }
+}
+
+pub fn set_path2<V>(d: &mut std::collections::HashMap<String, std::collections::HashMap<String, V>>, k1: impl AsRef<str>, k2: impl AsRef<str>, val: V) {
+    d.entry(k1.as_ref().to_string()).or_default().insert(k2.as_ref().to_string(), val);
+}
+
+pub fn set_path3<V>(d: &mut std::collections::HashMap<String, std::collections::HashMap<String, std::collections::HashMap<String, V>>>, k1: impl AsRef<str>, k2: impl AsRef<str>, k3: impl AsRef<str>, val: V) {
+    d.entry(k1.as_ref().to_string()).or_default()
+        .entry(k2.as_ref().to_string()).or_default()
+        .insert(k3.as_ref().to_string(), val);
+}
+
+// Ties on value go to the smallest key, so the result is deterministic.
+
+pub fn min_by_value<K: Clone + Ord + Default, V: Clone + PartialOrd + Default>(d: &std::collections::HashMap<K, V>) -> (bool, K, V) {
+    let mut best: Option<(&K, &V)> = None;
+    for (k, v) in d {
+        let better = match best {
+            None => true,
+            Some((bk, bv)) => v < bv || (v == bv && k < bk),
+        };
+        if better { best = Some((k, v)); }
+    }
+    best.map(|(k, v)| (true, k.clone(), v.clone())).unwrap_or((false, K::default(), V::default()))
+}
+
+pub fn max_by_value<K: Clone + Ord + Default, V: Clone + PartialOrd + Default>(d: &std::collections::HashMap<K, V>) -> (bool, K, V) {
+    let mut best: Option<(&K, &V)> = None;
+    for (k, v) in d {
+        let better = match best {
+            None => true,
+            Some((bk, bv)) => v > bv || (v == bv && k < bk),
+        };
+        if better { best = Some((k, v)); }
+    }
+    best.map(|(k, v)| (true, k.clone(), v.clone())).unwrap_or((false, K::default(), V::default()))
+}
+
+pub fn keys_with_value<K: Clone + Ord, V: PartialEq>(d: &std::collections::HashMap<K, V>, val: V) -> Vec<K> {
+    let mut out: Vec<K> = d.iter().filter(|(_, v)| **v == val).map(|(k, _)| k.clone()).collect();
+    out.sort();
+    out
+}
+
+pub fn retain_entries<K: Eq + std::hash::Hash + Clone, V: Clone>(d: &mut std::collections::HashMap<K, V>, f: impl Fn(K, V) -> bool) -> i32 {
+    let before = d.len();
+    d.retain(|k, v| f(k.clone(), v.clone()));
+    (before - d.len()) as i32
+}
+
+pub fn remove_keys<K: Eq + std::hash::Hash, V>(d: &mut std::collections::HashMap<K, V>, keys: Vec<K>) -> i32 {
+    keys.iter().filter(|k| d.remove(*k).is_some()).count() as i32
+}
+
+pub fn index_by<T: Clone, K: Eq + std::hash::Hash>(v: &[T], f: impl Fn(T) -> K) -> std::collections::HashMap<K, T> {
+    v.iter().map(|x| (f(x.clone()), x.clone())).collect()
+}
+
+pub fn index_by_unique<T: Clone, K: Eq + std::hash::Hash>(v: &[T], f: impl Fn(T) -> K) -> (bool, std::collections::HashMap<K, T>) {
+    let mut out = std::collections::HashMap::with_capacity(v.len());
+    for x in v {
+        let k = f(x.clone());
+        if out.contains_key(&k) { return (false, std::collections::HashMap::new()); }
+        out.insert(k, x.clone());
+    }
+    (true, out)
+}
+
+pub fn group_pairs_by<T: Clone, K: Eq + std::hash::Hash>(v: &[T], f: impl Fn(T) -> K) -> std::collections::HashMap<K, Vec<T>> {
+    let mut out: std::collections::HashMap<K, Vec<T>> = std::collections::HashMap::new();
+    for x in v {
+        out.entry(f(x.clone())).or_default().push(x.clone());
+    }
+    out
+}
+
+// ── Multimaps: HashMap<String, Vec<String>> ─────────────────
+// multimap_remove_item drops the key once its list becomes empty.
+
+pub fn multimap_get(d: &std::collections::HashMap<String, Vec<String>>, key: impl AsRef<str>) -> Vec<String> {
+    d.get(key.as_ref()).cloned().unwrap_or_default()
+}
+
+pub fn multimap_remove_item(d: &mut std::collections::HashMap<String, Vec<String>>, key: impl AsRef<str>, item: impl AsRef<str>) -> bool {
+    let key = key.as_ref();
+    let list = match d.get_mut(key) {
+        Some(list) => list,
+        None => return false,
+    };
+    let i = match list.iter().position(|x| x == item.as_ref()) {
+        Some(i) => i,
+        None => return false,
+    };
+    list.remove(i);
+    if list.is_empty() { d.remove(key); }
+    true
+}
+
+pub fn multimap_total_len(d: &std::collections::HashMap<String, Vec<String>>) -> i32 {
+    d.values().map(|v| v.len()).sum::<usize>() as i32
+}
+
+pub fn multimap_keys_sorted(d: &std::collections::HashMap<String, Vec<String>>) -> Vec<String> {
+    sorted_keys(d)
+}
+
+// By-value orderings accept PartialOrd (floats). NaN (a value not equal to
+// itself) sorts after every other value in both directions, so it never
+// reaches top_k ahead of a real score; ties fall back to ascending key.
+
+// `a != a` is the NaN test that works for any PartialOrd value.
+#[allow(clippy::eq_op)]
+fn dict_value_order<V: PartialOrd>(a: &V, b: &V, desc: bool) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a != a, b != b) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => {
+            let o = a.partial_cmp(b).unwrap_or(Ordering::Equal);
+            if desc { o.reverse() } else { o }
+        }
+    }
+}
+
+pub fn keys_sorted_by_value<K: Clone + Ord, V: Clone + PartialOrd>(d: &std::collections::HashMap<K, V>) -> Vec<K> {
+    let mut out: Vec<(&K, &V)> = d.iter().collect();
+    out.sort_by(|a, b| dict_value_order(a.1, b.1, false).then_with(|| a.0.cmp(b.0)));
+    out.into_iter().map(|(k, _)| k.clone()).collect()
+}
+
+pub fn keys_sorted_by_value_desc<K: Clone + Ord, V: Clone + PartialOrd>(d: &std::collections::HashMap<K, V>) -> Vec<K> {
+    let mut out: Vec<(&K, &V)> = d.iter().collect();
+    out.sort_by(|a, b| dict_value_order(a.1, b.1, true).then_with(|| a.0.cmp(b.0)));
+    out.into_iter().map(|(k, _)| k.clone()).collect()
+}
+
+pub fn top_k_by_value<K: Clone + Ord + Eq + std::hash::Hash, V: Clone + PartialOrd>(d: &std::collections::HashMap<K, V>, k: i32) -> Vec<(K, V)> {
+    keys_sorted_by_value_desc(d)
+        .into_iter()
+        .take(k.max(0) as usize)
+        .map(|key| { let v = d[&key].clone(); (key, v) })
+        .collect()
+}
+
+#[cfg(test)]
+mod dict_tests {
+    use super::*;
+
+    fn s(x: &str) -> String { String::from(x) }
+
+    fn sdict<V: Clone>(pairs: &[(&str, V)]) -> HashMap<String, V> {
+        pairs.iter().map(|(k, v)| (s(k), v.clone())).collect()
+    }
+
+    // ── get_or / try_get ────────────────────────────────────
+    #[test]
+    fn test_get_or_missing_returns_default() {
+        let d = sdict(&[("a", 1)]);
+        assert_eq!(get_or(&d, &s("z"), 42), 42);
+        assert_eq!(get_or_str(&d, "z", -1), -1);
+        assert_eq!(d, sdict(&[("a", 1)]));
+    }
+
+    #[test]
+    fn test_get_or_present_ignores_default() {
+        let d = sdict(&[("a", 1)]);
+        assert_eq!(get_or(&d, &s("a"), 42), 1);
+        assert_eq!(get_or_str(&d, "a", 42), 1);
+        assert_eq!(get_or_str(&d, s("a"), 42), 1);
+    }
+
+    #[test]
+    fn test_try_get() {
+        let d = sdict(&[("a", s("x"))]);
+        assert_eq!(try_get(&d, &s("a")), (true, s("x")));
+        assert_eq!(try_get(&d, &s("b")), (false, String::new()));
+        assert_eq!(d.len(), 1);
+    }
+
+    // ── setdefault / append_to ──────────────────────────────
+    #[test]
+    fn test_setdefault_absent_inserts() {
+        let mut d: HashMap<String, i32> = HashMap::new();
+        assert_eq!(setdefault(&mut d, s("a"), 5), 5);
+        assert_eq!(d, sdict(&[("a", 5)]));
+    }
+
+    #[test]
+    fn test_setdefault_present_keeps_value() {
+        let mut d = sdict(&[("a", 1)]);
+        assert_eq!(setdefault(&mut d, s("a"), 5), 1);
+        assert_eq!(d, sdict(&[("a", 1)]));
+    }
+
+    #[test]
+    fn test_append_to_accumulates_in_order() {
+        let mut d: HashMap<String, Vec<i32>> = HashMap::new();
+        append_to(&mut d, s("k"), 3);
+        append_to(&mut d, s("k"), 1);
+        append_to(&mut d, s("j"), 9);
+        append_to(&mut d, s("k"), 2);
+        assert_eq!(d[&s("k")], vec![3, 1, 2]);
+        assert_eq!(d[&s("j")], vec![9]);
+    }
+
+    // ── merge / update / merge_with ─────────────────────────
+    #[test]
+    fn test_merge_disjoint_and_overlapping() {
+        let a = sdict(&[("x", 1), ("y", 2)]);
+        assert_eq!(merge(a.clone(), sdict(&[("z", 3)])), sdict(&[("x", 1), ("y", 2), ("z", 3)]));
+        assert_eq!(merge(a, sdict(&[("y", 20), ("z", 3)])), sdict(&[("x", 1), ("y", 20), ("z", 3)]));
+    }
+
+    #[test]
+    fn test_merge_empty_inputs() {
+        let a = sdict(&[("x", 1)]);
+        assert_eq!(merge(a.clone(), HashMap::new()), a);
+        assert_eq!(merge(HashMap::new(), a.clone()), a);
+        assert!(merge::<String, i32>(HashMap::new(), HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_update_in_place() {
+        let mut d = sdict(&[("x", 1), ("y", 2)]);
+        update(&mut d, sdict(&[("y", 5), ("w", 0)]));
+        assert_eq!(d, sdict(&[("x", 1), ("y", 5), ("w", 0)]));
+    }
+
+    #[test]
+    fn test_merge_with_resolver_only_on_conflicts() {
+        let calls = std::cell::Cell::new(0);
+        let out = merge_with(sdict(&[("a", 1), ("b", 2)]), sdict(&[("b", 10), ("c", 3)]), |old, new| {
+            calls.set(calls.get() + 1);
+            old + new
+        });
+        assert_eq!(out, sdict(&[("a", 1), ("b", 12), ("c", 3)]));
+        assert_eq!(calls.get(), 1);
+    }
+
+    // ── map_values / map_keys / filter_entries ──────────────
+    #[test]
+    fn test_map_values() {
+        let d = sdict(&[("a", 1), ("b", 2)]);
+        assert_eq!(map_values(&d, |v| v * 10), sdict(&[("a", 10), ("b", 20)]));
+        assert!(map_values(&HashMap::<String, i32>::new(), |v| v).is_empty());
+    }
+
+    #[test]
+    fn test_map_keys_collision_keeps_one() {
+        let d = sdict(&[("a1", 1), ("a2", 2), ("b1", 3)]);
+        let out = map_keys(&d, |k: String| k[..1].to_string());
+        assert_eq!(out.len(), 2);
+        // Which colliding value survives is unspecified; it is one of them.
+        assert!(out[&s("a")] == 1 || out[&s("a")] == 2);
+        assert_eq!(out[&s("b")], 3);
+        assert!(map_keys(&HashMap::<String, i32>::new(), |k| k).is_empty());
+    }
+
+    #[test]
+    fn test_filter_entries() {
+        let d = sdict(&[("a", 1), ("b", 2), ("c", 3)]);
+        assert_eq!(filter_entries(&d, |k, v| k != "b" && v > 1), sdict(&[("c", 3)]));
+        assert!(filter_entries(&d, |_, _| false).is_empty());
+        assert_eq!(d.len(), 3);
+    }
+
+    // ── sorted_keys / sorted_entries / entries_by_value ─────
+    fn tie_dict() -> HashMap<String, i32> {
+        sdict(&[("d", 2), ("a", 3), ("c", 1), ("b", 2)])
+    }
+
+    #[test]
+    fn test_sorted_keys() {
+        let d = tie_dict();
+        assert_eq!(sorted_keys(&d), vec![s("a"), s("b"), s("c"), s("d")]);
+        assert_eq!(sorted_keys_desc(&d), vec![s("d"), s("c"), s("b"), s("a")]);
+    }
+
+    #[test]
+    fn test_sorted_entries() {
+        let d = tie_dict();
+        assert_eq!(sorted_entries(&d), vec![(s("a"), 3), (s("b"), 2), (s("c"), 1), (s("d"), 2)]);
+        assert_eq!(sorted_entries_desc(&d), vec![(s("d"), 2), (s("c"), 1), (s("b"), 2), (s("a"), 3)]);
+    }
+
+    #[test]
+    fn test_entries_by_value_ties_by_key() {
+        let d = tie_dict();
+        assert_eq!(entries_by_value(&d), vec![(s("c"), 1), (s("b"), 2), (s("d"), 2), (s("a"), 3)]);
+        assert_eq!(entries_by_value_desc(&d), vec![(s("a"), 3), (s("b"), 2), (s("d"), 2), (s("c"), 1)]);
+    }
+
+    // ── counters ────────────────────────────────────────────
+    #[test]
+    fn test_counter_from_tokens() {
+        let toks: Vec<String> = "a b a c a b".split(' ').map(s).collect();
+        let c = counter_from(&toks);
+        assert_eq!(c, sdict(&[("a", 3), ("b", 2), ("c", 1)]));
+        assert_eq!(counter_total(&c), 6);
+        assert!(counter_from::<String>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_incr_unseen_key() {
+        let mut c: HashMap<String, i32> = HashMap::new();
+        incr(&mut c, s("new"));
+        incr_by(&mut c, s("other"), 5);
+        assert_eq!(c, sdict(&[("new", 1), ("other", 5)]));
+    }
+
+    #[test]
+    fn test_decr_to_zero_keeps_entry() {
+        let mut c = sdict(&[("a", 2)]);
+        decr(&mut c, s("a"));
+        incr_by(&mut c, s("a"), -1);
+        assert_eq!(c, sdict(&[("a", 0)]));
+        decr(&mut c, s("b"));
+        assert_eq!(c[&s("b")], -1);
+        assert_eq!(counter_total(&c), -1);
+    }
+
+    #[test]
+    fn test_counter_total_is_i64() {
+        let c = sdict(&[("a", i32::MAX), ("b", i32::MAX)]);
+        assert_eq!(counter_total(&c), 2 * i32::MAX as i64);
+    }
+
+    // ── pop_or / try_pop / pop_any ──────────────────────────
+    #[test]
+    fn test_pop_missing_key_untouched() {
+        let mut d = sdict(&[("a", 1)]);
+        assert_eq!(pop_or(&mut d, &s("z"), 7), 7);
+        assert_eq!(try_pop(&mut d, &s("z")), (false, 0));
+        assert_eq!(d, sdict(&[("a", 1)]));
+    }
+
+    #[test]
+    fn test_pop_present_key_removes() {
+        let mut d = sdict(&[("a", 1), ("b", 2)]);
+        assert_eq!(pop_or(&mut d, &s("a"), 7), 1);
+        assert_eq!(try_pop(&mut d, &s("b")), (true, 2));
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_pop_any_empty() {
+        let mut d: HashMap<String, i32> = HashMap::new();
+        assert_eq!(pop_any(&mut d), (false, String::new(), 0));
+    }
+
+    #[test]
+    fn test_pop_any_drains_each_entry_once() {
+        let mut d = sdict(&[("a", 1), ("b", 2), ("c", 3)]);
+        let mut seen = Vec::new();
+        loop {
+            let (ok, k, v) = pop_any(&mut d);
+            if !ok { break; }
+            seen.push((k, v));
+        }
+        seen.sort();
+        assert_eq!(seen, vec![(s("a"), 1), (s("b"), 2), (s("c"), 3)]);
+        assert!(d.is_empty());
+    }
+
+    // ── nested paths ────────────────────────────────────────
+    type D2 = HashMap<String, HashMap<String, i32>>;
+    type D3 = HashMap<String, HashMap<String, HashMap<String, i32>>>;
+
+    #[test]
+    fn test_get_path_missing_at_each_level() {
+        let mut d: D3 = HashMap::new();
+        assert_eq!(get_path3(&d, "a", "b", "c"), (false, 0));
+        set_path3(&mut d, "a", "b", "c", 1);
+        assert_eq!(get_path3(&d, "x", "b", "c"), (false, 0));
+        assert_eq!(get_path3(&d, "a", "x", "c"), (false, 0));
+        assert_eq!(get_path3(&d, "a", "b", "x"), (false, 0));
+        assert_eq!(get_path3(&d, "a", "b", "c"), (true, 1));
+
+        let mut d2: D2 = HashMap::new();
+        set_path2(&mut d2, "a", "b", 2);
+        assert_eq!(get_path2(&d2, "x", "b"), (false, 0));
+        assert_eq!(get_path2(&d2, "a", "x"), (false, 0));
+        assert_eq!(get_path2(&d2, s("a"), s("b")), (true, 2));
+    }
+
+    #[test]
+    fn test_set_path_overwrites_leaf() {
+        let mut d: D2 = HashMap::new();
+        set_path2(&mut d, "layout", "dir", 1);
+        set_path2(&mut d, "layout", "dir", 2);
+        set_path2(&mut d, "layout", "gap", 3);
+        assert_eq!(get_path2(&d, "layout", "dir"), (true, 2));
+        assert_eq!(d[&s("layout")].len(), 2);
+    }
+
+    #[test]
+    fn test_set_path3_creates_two_levels() {
+        let mut d: D3 = HashMap::new();
+        set_path3(&mut d, "a", "b", "c", 9);
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[&s("a")].len(), 1);
+        assert_eq!(d[&s("a")][&s("b")][&s("c")], 9);
+    }
+
+    // ── min_by_value / max_by_value / keys_with_value ───────
+    #[test]
+    fn test_min_max_by_value_ties_pick_smallest_key() {
+        let d = sdict(&[("c", 1), ("a", 5), ("b", 1), ("d", 5)]);
+        assert_eq!(min_by_value(&d), (true, s("b"), 1));
+        assert_eq!(max_by_value(&d), (true, s("a"), 5));
+    }
+
+    #[test]
+    fn test_min_max_by_value_single_and_empty() {
+        let d = sdict(&[("only", 2.5f32)]);
+        assert_eq!(min_by_value(&d), (true, s("only"), 2.5));
+        assert_eq!(max_by_value(&d), (true, s("only"), 2.5));
+        let e: HashMap<String, i32> = HashMap::new();
+        assert_eq!(min_by_value(&e), (false, String::new(), 0));
+        assert_eq!(max_by_value(&e), (false, String::new(), 0));
+    }
+
+    #[test]
+    fn test_keys_with_value_sorted() {
+        let d = sdict(&[("c", 1), ("a", 1), ("b", 2)]);
+        assert_eq!(keys_with_value(&d, 1), vec![s("a"), s("c")]);
+        assert!(keys_with_value(&d, 9).is_empty());
+    }
+
+    // ── retain_entries / remove_keys ────────────────────────
+    #[test]
+    fn test_retain_entries_keep_nothing_and_everything() {
+        let mut d = sdict(&[("a", 1), ("b", 2), ("c", 3)]);
+        assert_eq!(retain_entries(&mut d, |_, _| true), 0);
+        assert_eq!(d.len(), 3);
+        assert_eq!(retain_entries(&mut d, |k, v| k == "a" || v == 3), 1);
+        assert_eq!(d, sdict(&[("a", 1), ("c", 3)]));
+        assert_eq!(retain_entries(&mut d, |_, _| false), 2);
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_remove_keys_counts_only_present() {
+        let mut d = sdict(&[("a", 1), ("b", 2)]);
+        assert_eq!(remove_keys(&mut d, vec![s("a"), s("z"), s("a")]), 1);
+        assert_eq!(d, sdict(&[("b", 2)]));
+        assert_eq!(remove_keys(&mut d, vec![]), 0);
+    }
+
+    // ── index_by / index_by_unique / group_pairs_by ─────────
+    #[test]
+    fn test_index_by_last_wins() {
+        let v = vec![(1, s("a")), (2, s("b")), (1, s("c"))];
+        let idx = index_by(&v, |r: (i32, String)| r.0);
+        assert_eq!(idx.len(), 2);
+        assert_eq!(idx[&1], (1, s("c")));
+        assert!(index_by(&[] as &[(i32, String)], |r| r.0).is_empty());
+    }
+
+    #[test]
+    fn test_index_by_unique_fails_on_duplicates() {
+        let v = vec![(1, s("a")), (2, s("b")), (1, s("c"))];
+        let (ok, idx) = index_by_unique(&v, |r: (i32, String)| r.0);
+        assert!(!ok && idx.is_empty());
+        let (ok, idx) = index_by_unique(&v[..2], |r: (i32, String)| r.0);
+        assert!(ok);
+        assert_eq!(idx[&2], (2, s("b")));
+        let (ok, idx) = index_by_unique(&[] as &[i32], |x| x);
+        assert!(ok && idx.is_empty());
+    }
+
+    #[test]
+    fn test_group_pairs_by_keeps_order() {
+        let v = vec![3, 4, 5, 6, 7];
+        let g = group_pairs_by(&v, |x| x % 2);
+        assert_eq!(g[&1], vec![3, 5, 7]);
+        assert_eq!(g[&0], vec![4, 6]);
+        assert!(group_pairs_by(&[] as &[i32], |x| x).is_empty());
+    }
+
+    // ── multimaps ───────────────────────────────────────────
+    fn mm() -> HashMap<String, Vec<String>> {
+        sdict(&[("a", vec![s("x"), s("y")]), ("b", vec![s("z")])])
+    }
+
+    #[test]
+    fn test_multimap_get_missing_key() {
+        let d = mm();
+        assert_eq!(multimap_get(&d, "a"), vec![s("x"), s("y")]);
+        assert!(multimap_get(&d, "nope").is_empty());
+    }
+
+    #[test]
+    fn test_multimap_remove_item() {
+        let mut d = mm();
+        assert!(!multimap_remove_item(&mut d, "nope", "x"));
+        assert!(!multimap_remove_item(&mut d, "a", "z"));
+        assert!(multimap_remove_item(&mut d, "a", "x"));
+        assert_eq!(multimap_get(&d, "a"), vec![s("y")]);
+    }
+
+    #[test]
+    fn test_multimap_remove_last_item_drops_key() {
+        let mut d = mm();
+        assert!(multimap_remove_item(&mut d, "b", "z"));
+        assert!(!d.contains_key("b"));
+        assert_eq!(multimap_keys_sorted(&d), vec![s("a")]);
+    }
+
+    #[test]
+    fn test_multimap_totals() {
+        let d = mm();
+        assert_eq!(multimap_total_len(&d), 3);
+        assert_eq!(multimap_keys_sorted(&d), vec![s("a"), s("b")]);
+        assert_eq!(multimap_total_len(&HashMap::new()), 0);
+    }
+
+    // ── keys_sorted_by_value / top_k_by_value ───────────────
+    #[test]
+    fn test_keys_sorted_by_value_ties() {
+        let d = sdict(&[("d", 2), ("a", 3), ("c", 1), ("b", 2)]);
+        assert_eq!(keys_sorted_by_value(&d), vec![s("c"), s("b"), s("d"), s("a")]);
+        assert_eq!(keys_sorted_by_value_desc(&d), vec![s("a"), s("b"), s("d"), s("c")]);
+    }
+
+    #[test]
+    fn test_keys_sorted_by_float_value() {
+        let d = sdict(&[("x", 0.5f32), ("y", -1.25), ("z", 0.75)]);
+        assert_eq!(keys_sorted_by_value(&d), vec![s("y"), s("x"), s("z")]);
+        assert_eq!(top_k_by_value(&d, 2), vec![(s("z"), 0.75), (s("x"), 0.5)]);
+    }
+
+    #[test]
+    fn test_top_k_by_value_bounds() {
+        let d = sdict(&[("a", 1), ("b", 2)]);
+        assert_eq!(top_k_by_value(&d, 10), vec![(s("b"), 2), (s("a"), 1)]);
+        assert!(top_k_by_value(&d, 0).is_empty());
+        assert!(top_k_by_value(&d, -1).is_empty());
+        assert!(top_k_by_value(&HashMap::<String, i32>::new(), 3).is_empty());
+    }
+
+    #[test]
+    fn test_keys_sorted_by_value_nan_last() {
+        let d = sdict(&[("n1", f32::NAN), ("a", 2.0), ("n2", f32::NAN), ("b", 1.0), ("c", 3.0), ("d", 1.0)]);
+        assert_eq!(keys_sorted_by_value(&d), vec![s("b"), s("d"), s("a"), s("c"), s("n1"), s("n2")]);
+        assert_eq!(keys_sorted_by_value_desc(&d), vec![s("c"), s("a"), s("b"), s("d"), s("n1"), s("n2")]);
+        assert_eq!(top_k_by_value(&d, 2), vec![(s("c"), 3.0), (s("a"), 2.0)]);
+    }
+
+    #[test]
+    fn test_keys_sorted_by_value_many_nans_is_deterministic() {
+        let d: HashMap<String, f32> = (0..200)
+            .map(|i| (format!("k{:03}", i), if i % 3 == 0 { f32::NAN } else { (i % 7) as f32 }))
+            .collect();
+        let asc = keys_sorted_by_value(&d);
+        assert_eq!(asc, keys_sorted_by_value(&d.clone()));
+        let (real, nans) = asc.split_at(asc.len() - 67);
+        assert!(nans.iter().all(|k| d[k].is_nan()));
+        assert!(real.windows(2).all(|w| d[&w[0]] <= d[&w[1]]));
+        let top = top_k_by_value(&d, 5);
+        assert!(top.iter().all(|(_, v)| *v == 6.0));
+    }
+}
@@ -27,7 +27,9 @@ macro_rules! len {
 pub trait HomunLen { fn homun_len(&self) -> usize; }
 impl<T> HomunLen for Vec<T>                                { fn homun_len(&self) -> usize { self.len() } }
 impl<K, V> HomunLen for std::collections::HashMap<K, V>    { fn homun_len(&self) -> usize { self.len() } }
+impl<K, V> HomunLen for std::collections::BTreeMap<K, V>   { fn homun_len(&self) -> usize { self.len() } }
 impl<T> HomunLen for std::collections::HashSet<T>          { fn homun_len(&self) -> usize { self.len() } }
+impl<T> HomunLen for std::collections::VecDeque<T>         { fn homun_len(&self) -> usize { self.len() } }
 impl HomunLen for String                                    { fn homun_len(&self) -> usize { self.len() } }
 impl HomunLen for str                                       { fn homun_len(&self) -> usize { self.len() } }
 
@@ -35,7 +37,14 @@ impl HomunLen for str                                       { fn homun_len(&self
 
 macro_rules! filter {
     ($v:expr, $f:expr) => {
-        ($v).iter().cloned().filter(|x| ($f)(x.clone())).collect::<Vec<_>>()
+        ($v).iter().filter(|x| ($f)((*x).clone())).cloned().collect::<Vec<_>>()
+    };
+}
+
+// filter_ref! hands the predicate &T, so only kept elements are cloned.
+macro_rules! filter_ref {
+    ($v:expr, $f:expr) => {
+        ($v).iter().filter(|x| ($f)(*x)).cloned().collect::<Vec<_>>()
     };
 }
 
@@ -57,6 +66,7 @@ include!("str.rs");
 include!("math.rs");
 include!("collection.rs");
 include!("dict.rs");
+include!("odict.rs");
 include!("stack.rs");
 include!("deque.rs");
 include!("io.rs");
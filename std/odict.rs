@@ -0,0 +1,106 @@
+// ============================================================
+// Homun Ordered Dict Library — included by std.rs
+// ============================================================
+
+// BTreeMap is already imported by builtin.rs
+// Keys are String and iteration is always in key order.
+
+pub fn odict_new<V>() -> BTreeMap<String, V> {
+    BTreeMap::new()
+}
+
+pub fn odict_insert<V>(d: &mut BTreeMap<String, V>, key: impl AsRef<str>, val: V) {
+    d.insert(key.as_ref().to_string(), val);
+}
+
+pub fn odict_get_or<V: Clone>(d: &BTreeMap<String, V>, key: impl AsRef<str>, default: V) -> V {
+    d.get(key.as_ref()).cloned().unwrap_or(default)
+}
+
+pub fn odict_remove<V: Default>(d: &mut BTreeMap<String, V>, key: impl AsRef<str>) -> (bool, V) {
+    match d.remove(key.as_ref()) {
+        Some(v) => (true, v),
+        None => (false, V::default()),
+    }
+}
+
+pub fn odict_keys<V>(d: &BTreeMap<String, V>) -> Vec<String> {
+    d.keys().cloned().collect()
+}
+
+pub fn odict_values<V: Clone>(d: &BTreeMap<String, V>) -> Vec<V> {
+    d.values().cloned().collect()
+}
+
+pub fn odict_entries<V: Clone>(d: &BTreeMap<String, V>) -> Vec<(String, V)> {
+    d.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+pub fn odict_len<V>(d: &BTreeMap<String, V>) -> i32 {
+    d.len() as i32
+}
+
+pub fn odict_first<V: Clone + Default>(d: &BTreeMap<String, V>) -> (bool, String, V) {
+    match d.first_key_value() {
+        Some((k, v)) => (true, k.clone(), v.clone()),
+        None => (false, String::new(), V::default()),
+    }
+}
+
+pub fn odict_last<V: Clone + Default>(d: &BTreeMap<String, V>) -> (bool, String, V) {
+    match d.last_key_value() {
+        Some((k, v)) => (true, k.clone(), v.clone()),
+        None => (false, String::new(), V::default()),
+    }
+}
+
+#[cfg(test)]
+mod odict_tests {
+    use super::*;
+
+    #[test]
+    fn test_odict_iterates_in_key_order() {
+        let mut d = odict_new();
+        odict_insert(&mut d, "pear", 3);
+        odict_insert(&mut d, "apple", 1);
+        odict_insert(&mut d, String::from("fig"), 2);
+        assert_eq!(odict_keys(&d), vec!["apple", "fig", "pear"]);
+        assert_eq!(odict_values(&d), vec![1, 2, 3]);
+        assert_eq!(odict_entries(&d), vec![(String::from("apple"), 1), (String::from("fig"), 2), (String::from("pear"), 3)]);
+    }
+
+    #[test]
+    fn test_odict_get_remove() {
+        let mut d = odict_new();
+        odict_insert(&mut d, "a", 1);
+        odict_insert(&mut d, "a", 5);
+        assert_eq!(odict_len(&d), 1);
+        assert_eq!(odict_get_or(&d, "a", 0), 5);
+        assert_eq!(odict_get_or(&d, "b", 0), 0);
+        assert_eq!(odict_remove(&mut d, "b"), (false, 0));
+        assert_eq!(odict_remove(&mut d, "a"), (true, 5));
+        assert_eq!(odict_len(&d), 0);
+    }
+
+    #[test]
+    fn test_odict_first_last() {
+        let mut d = odict_new();
+        assert_eq!(odict_first::<i32>(&d), (false, String::new(), 0));
+        assert_eq!(odict_last::<i32>(&d), (false, String::new(), 0));
+        odict_insert(&mut d, "m", 2);
+        odict_insert(&mut d, "z", 3);
+        odict_insert(&mut d, "b", 1);
+        assert_eq!(odict_first(&d), (true, String::from("b"), 1));
+        assert_eq!(odict_last(&d), (true, String::from("z"), 3));
+    }
+
+    #[test]
+    fn test_odict_len_and_in() {
+        let mut d = odict_new();
+        odict_insert(&mut d, "k", 1);
+        assert_eq!(len!(d), 1);
+        assert!(homun_in!(String::from("k"), d));
+        assert!(!homun_in!(String::from("x"), d));
+        assert!(homun_in!("k", d));
+    }
+}
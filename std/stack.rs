@@ -21,3 +21,210 @@ pub fn stack_peek<T: Clone>(s: &Vec<T>) -> Option<T> {
 pub fn stack_is_empty<T>(s: &Vec<T>) -> bool {
     s.is_empty()
 }
+
+pub fn stack_peek_t<T: Clone + Default>(s: &[T]) -> (bool, T) {
+    match s.last() {
+        Some(x) => (true, x.clone()),
+        None => (false, T::default()),
+    }
+}
+
+pub fn stack_len<T>(s: &[T]) -> i32 {
+    s.len() as i32
+}
+
+pub fn stack_depth_at_most<T>(s: &[T], n: i32) -> bool {
+    n >= 0 && s.len() <= n as usize
+}
+
+// stack_from_vec puts v[0] on TOP, so a DFS seeded with roots pops them
+// in list order.  stack_to_vec_pop_order is its inverse (top first).
+
+pub fn stack_from_vec<T>(v: Vec<T>) -> Vec<T> {
+    let mut s = v; s.reverse(); s
+}
+
+pub fn stack_to_vec_pop_order<T: Clone>(s: &[T]) -> Vec<T> {
+    s.iter().rev().cloned().collect()
+}
+
+pub fn stack_clear<T>(s: &mut Vec<T>) {
+    s.clear();
+}
+
+pub fn stack_peek_n<T: Clone>(s: &[T], n: i32) -> Option<T> {
+    if n < 0 || n as usize >= s.len() { return None; }
+    s.get(s.len() - 1 - n as usize).cloned()
+}
+
+pub fn stack_top_k<T: Clone>(s: &[T], k: i32) -> Vec<T> {
+    s.iter().rev().take(k.max(0) as usize).cloned().collect()
+}
+
+// Both are a linear scan: O(n) in the stack depth per call.
+
+pub fn stack_contains<T: PartialEq>(s: &[T], item: &T) -> bool {
+    s.contains(item)
+}
+
+pub fn stack_index_from_top<T: PartialEq>(s: &[T], item: &T) -> i32 {
+    s.iter().rev().position(|x| x == item).map(|i| i as i32).unwrap_or(-1)
+}
+
+// Underflow returns false and leaves the stack unchanged.
+
+pub fn stack_dup<T: Clone>(s: &mut Vec<T>) -> bool {
+    match s.last().cloned() {
+        Some(x) => { s.push(x); true }
+        None => false,
+    }
+}
+
+pub fn stack_swap_top<T>(s: &mut [T]) -> bool {
+    let n = s.len();
+    if n < 2 { return false; }
+    s.swap(n - 1, n - 2);
+    true
+}
+
+pub fn stack_pop_n<T>(s: &mut Vec<T>, n: i32) -> Vec<T> {
+    let k = (n.max(0) as usize).min(s.len());
+    let mut out = s.split_off(s.len() - k);
+    out.reverse();
+    out
+}
+
+#[cfg(test)]
+mod stack_tests {
+    use super::*;
+
+    fn abc() -> Vec<String> {
+        let mut s = stack_new();
+        for x in ["a", "b", "c"] { stack_push(&mut s, String::from(x)); }
+        s
+    }
+
+    // ── len / depth / peek_t ────────────────────────────────
+    #[test]
+    fn test_stack_len_and_depth() {
+        let empty: Vec<i32> = stack_new();
+        assert_eq!(stack_len(&empty), 0);
+        assert!(stack_depth_at_most(&empty, 0));
+        assert!(!stack_depth_at_most(&empty, -1));
+        let s = abc();
+        assert_eq!(stack_len(&s), 3);
+        assert!(stack_depth_at_most(&s, 3));
+        assert!(!stack_depth_at_most(&s, 2));
+    }
+
+    #[test]
+    fn test_stack_peek_t() {
+        let empty: Vec<String> = stack_new();
+        assert_eq!(stack_peek_t(&empty), (false, String::new()));
+        let s = abc();
+        assert_eq!(stack_peek_t(&s), (true, String::from("c")));
+        assert_eq!(stack_len(&s), 3);
+    }
+
+    // ── from_vec / to_vec_pop_order / clear ─────────────────
+    #[test]
+    fn test_stack_from_vec_first_item_on_top() {
+        let mut s = stack_from_vec(vec![1, 2, 3]);
+        assert_eq!(stack_peek(&s), Some(1));
+        assert_eq!(stack_pop(&mut s), Some(1));
+        assert_eq!(stack_pop(&mut s), Some(2));
+        assert_eq!(stack_pop(&mut s), Some(3));
+        assert!(stack_from_vec(Vec::<i32>::new()).is_empty());
+    }
+
+    #[test]
+    fn test_stack_to_vec_pop_order_is_inverse() {
+        let s = abc();
+        assert_eq!(stack_to_vec_pop_order(&s), vec!["c", "b", "a"]);
+        assert_eq!(stack_len(&s), 3);
+        let v = vec![4, 5, 6];
+        assert_eq!(stack_to_vec_pop_order(&stack_from_vec(v.clone())), v);
+    }
+
+    #[test]
+    fn test_stack_clear() {
+        let mut s = abc();
+        stack_clear(&mut s);
+        assert!(stack_is_empty(&s));
+        stack_push(&mut s, String::from("z"));
+        assert_eq!(stack_len(&s), 1);
+    }
+
+    // ── peek_n / top_k ──────────────────────────────────────
+    #[test]
+    fn test_stack_peek_n() {
+        let s = abc();
+        assert_eq!(stack_peek_n(&s, 0), Some(String::from("c")));
+        assert_eq!(stack_peek_n(&s, 2), Some(String::from("a")));
+        assert_eq!(stack_peek_n(&s, 3), None);
+        assert_eq!(stack_peek_n(&s, -1), None);
+        assert_eq!(stack_peek_n::<i32>(&[], 0), None);
+    }
+
+    #[test]
+    fn test_stack_top_k() {
+        let s = abc();
+        assert!(stack_top_k(&s, 0).is_empty());
+        assert!(stack_top_k(&s, -2).is_empty());
+        assert_eq!(stack_top_k(&s, 2), vec!["c", "b"]);
+        assert_eq!(stack_top_k(&s, 10), vec!["c", "b", "a"]);
+        assert_eq!(stack_len(&s), 3);
+    }
+
+    // ── contains / index_from_top ───────────────────────────
+    #[test]
+    fn test_stack_index_from_top_nearest_wins() {
+        let s = vec![1, 2, 1, 3];
+        assert_eq!(stack_index_from_top(&s, &3), 0);
+        assert_eq!(stack_index_from_top(&s, &1), 1);
+        assert_eq!(stack_index_from_top(&s, &2), 2);
+        assert_eq!(stack_index_from_top(&s, &9), -1);
+        assert!(stack_contains(&s, &2));
+        assert!(!stack_contains(&s, &9));
+    }
+
+    #[test]
+    fn test_stack_contains_empty() {
+        let empty: Vec<String> = stack_new();
+        assert!(!stack_contains(&empty, &String::from("a")));
+        assert_eq!(stack_index_from_top(&empty, &String::from("a")), -1);
+    }
+
+    // ── dup / swap_top / pop_n ──────────────────────────────
+    #[test]
+    fn test_stack_dup_and_swap_underflow() {
+        let mut empty: Vec<i32> = stack_new();
+        assert!(!stack_dup(&mut empty));
+        assert!(!stack_swap_top(&mut empty));
+        assert!(empty.is_empty());
+        let mut one = vec![7];
+        assert!(!stack_swap_top(&mut one));
+        assert_eq!(one, vec![7]);
+    }
+
+    #[test]
+    fn test_stack_dup_and_swap() {
+        let mut s = abc();
+        assert!(stack_dup(&mut s));
+        assert_eq!(stack_to_vec_pop_order(&s), vec!["c", "c", "b", "a"]);
+        stack_pop(&mut s);
+        assert!(stack_swap_top(&mut s));
+        assert_eq!(stack_to_vec_pop_order(&s), vec!["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_stack_pop_n_top_first() {
+        let mut s = vec![1, 2, 3, 4];
+        assert_eq!(stack_pop_n(&mut s, 2), vec![4, 3]);
+        assert_eq!(s, vec![1, 2]);
+        assert!(stack_pop_n(&mut s, 0).is_empty());
+        assert!(stack_pop_n(&mut s, -1).is_empty());
+        assert_eq!(stack_pop_n(&mut s, 5), vec![2, 1]);
+        assert!(s.is_empty());
+    }
+}
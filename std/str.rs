@@ -6,6 +6,23 @@ pub fn split(s: impl AsRef<str>, sep: impl AsRef<str>) -> Vec<String> {
     s.as_ref().split(sep.as_ref()).map(|x| x.to_string()).collect()
 }
 
+// At most n parts, the last holding the unsplit rest (Rust splitn numbering);
+// n <= 0 splits fully.
+pub fn split_limit(s: impl AsRef<str>, sep: impl AsRef<str>, n: i32) -> Vec<String> {
+    if n <= 0 { return split(s, sep); }
+    s.as_ref().splitn(n as usize, sep.as_ref()).map(|x| x.to_string()).collect()
+}
+
+// Split on runs of Unicode whitespace; never yields empty fields.
+pub fn split_ws(s: impl AsRef<str>) -> Vec<String> {
+    s.as_ref().split_whitespace().map(|x| x.to_string()).collect()
+}
+
+// Split on a literal separator, dropping empty fields.
+pub fn split_nonempty(s: impl AsRef<str>, sep: impl AsRef<str>) -> Vec<String> {
+    s.as_ref().split(sep.as_ref()).filter(|x| !x.is_empty()).map(|x| x.to_string()).collect()
+}
+
 pub fn join(v: impl AsRef<[String]>, sep: impl AsRef<str>) -> String {
     v.as_ref().join(sep.as_ref())
 }
@@ -30,18 +47,45 @@ pub fn find(s: impl AsRef<str>, sub: impl AsRef<str>) -> i32 {
     s.as_ref().find(sub.as_ref()).map(|i| i as i32).unwrap_or(-1)
 }
 
+pub fn rfind(s: impl AsRef<str>, sub: impl AsRef<str>) -> i32 {
+    s.as_ref().rfind(sub.as_ref()).map(|i| i as i32).unwrap_or(-1)
+}
+
+// First match at or after byte offset `start` (negative = 0); -1 if none,
+// if start is past the end, or if start is not on a char boundary.
+pub fn find_from(s: impl AsRef<str>, sub: impl AsRef<str>, start: i32) -> i32 {
+    let s = s.as_ref();
+    let start = start.max(0) as usize;
+    match s.get(start..) {
+        Some(rest) => rest.find(sub.as_ref()).map(|i| (start + i) as i32).unwrap_or(-1),
+        None => -1,
+    }
+}
+
 pub fn contains(s: impl AsRef<str>, sub: impl AsRef<str>) -> bool { s.as_ref().contains(sub.as_ref()) }
 
 pub fn repeat(s: impl AsRef<str>, n: i32) -> String { s.as_ref().repeat(n as usize) }
 
+// Byte offsets, matching find. An offset inside a multi-byte character is
+// moved back to that character's start instead of yielding "".
 pub fn substr(s: impl AsRef<str>, start: i32, end: i32) -> String {
     let s = s.as_ref();
     let len = s.len() as i32;
-    let s_ = if start < 0 { (len + start).max(0) } else { start.min(len) } as usize;
-    let e_ = if end < 0 { (len + end).max(0) } else { end.min(len) } as usize;
+    let floor = |mut i: usize| { while !s.is_char_boundary(i) { i -= 1; } i };
+    let s_ = floor(if start < 0 { (len + start).max(0) } else { start.min(len) } as usize);
+    let e_ = floor(if end < 0 { (len + end).max(0) } else { end.min(len) } as usize);
     s.get(s_..e_).unwrap_or("").to_string()
 }
 
+// Same as substr, but start/end are character indices (like char_at).
+pub fn substr_chars(s: impl AsRef<str>, start: i32, end: i32) -> String {
+    let s = s.as_ref();
+    let len = s.chars().count() as i32;
+    let s_ = if start < 0 { (len + start).max(0) } else { start.min(len) };
+    let e_ = if end < 0 { (len + end).max(0) } else { end.min(len) };
+    s.chars().skip(s_ as usize).take((e_ - s_).max(0) as usize).collect()
+}
+
 pub fn strip_prefix(s: impl AsRef<str>, prefix: impl AsRef<str>) -> String {
     let s = s.as_ref();
     s.strip_prefix(prefix.as_ref()).unwrap_or(s).to_string()
@@ -96,3 +140,244 @@ pub fn parse_int(s: impl AsRef<str>) -> i32 {
 pub fn parse_float(s: impl AsRef<str>) -> f32 {
     s.as_ref().trim().parse::<f32>().unwrap_or(0.0)
 }
+
+// Strict variants: (false, 0) on empty input, trailing garbage ("12abc") or overflow.
+// Floats follow Rust syntax, so "1e3", "inf" and "nan" parse successfully.
+pub fn try_parse_int(s: impl AsRef<str>) -> (bool, i32) {
+    match s.as_ref().trim().parse::<i32>() {
+        Ok(n) => (true, n),
+        Err(_) => (false, 0),
+    }
+}
+
+pub fn try_parse_float(s: impl AsRef<str>) -> (bool, f32) {
+    match s.as_ref().trim().parse::<f32>() {
+        Ok(x) => (true, x),
+        Err(_) => (false, 0.0),
+    }
+}
+
+// Explicit radix 2–36; an out-of-range radix fails like bad digits.
+pub fn try_parse_int_radix(s: impl AsRef<str>, radix: i32) -> (bool, i32) {
+    if !(2..=36).contains(&radix) { return (false, 0); }
+    match i32::from_str_radix(s.as_ref().trim(), radix as u32) {
+        Ok(n) => (true, n),
+        Err(_) => (false, 0),
+    }
+}
+
+pub fn parse_int_radix(s: impl AsRef<str>, radix: i32) -> i32 { try_parse_int_radix(s, radix).1 }
+
+// Optional sign, then 0x / 0o / 0b (any case) or plain decimal: "-0b101" -> -5.
+pub fn try_parse_int_auto(s: impl AsRef<str>) -> (bool, i32) {
+    let t = s.as_ref().trim();
+    let (neg, t) = match t.as_bytes().first() {
+        Some(b'-') => (true, &t[1..]),
+        Some(b'+') => (false, &t[1..]),
+        _ => (false, t),
+    };
+    let (radix, digits) = match t.get(..2).map(|p| p.to_ascii_lowercase()).as_deref() {
+        Some("0x") => (16, &t[2..]),
+        Some("0o") => (8, &t[2..]),
+        Some("0b") => (2, &t[2..]),
+        _ => (10, t),
+    };
+    if digits.starts_with(['+', '-']) { return (false, 0); }
+    let Ok(mag) = i64::from_str_radix(digits, radix) else { return (false, 0) };
+    match i32::try_from(if neg { -mag } else { mag }) {
+        Ok(n) => (true, n),
+        Err(_) => (false, 0),
+    }
+}
+
+pub fn parse_int_auto(s: impl AsRef<str>) -> i32 { try_parse_int_auto(s).1 }
+
+#[cfg(test)]
+mod str_tests {
+    use super::*;
+
+    // ── split_ws / split_nonempty ───────────────────────────
+    #[test]
+    fn test_split_ws_leading_trailing_and_tabs() {
+        assert_eq!(split_ws("  A   -->\tB  "), vec!["A", "-->", "B"]);
+        assert_eq!(split_ws("a\t \tb\n c"), vec!["a", "b", "c"]);
+        assert!(split_ws("").is_empty());
+        assert!(split_ws(" \t\n ").is_empty());
+        assert_eq!(split_ws(String::from("x")), vec!["x"]);
+    }
+
+    #[test]
+    fn test_split_nonempty() {
+        assert_eq!(split_nonempty(",a,,b,", ","), vec!["a", "b"]);
+        assert_eq!(split_nonempty("a  b", " "), vec!["a", "b"]);
+        assert!(split_nonempty("", ",").is_empty());
+        assert!(split_nonempty(",,,", ",").is_empty());
+    }
+
+    // ── rfind / find_from ───────────────────────────────────
+    #[test]
+    fn test_rfind_repeated() {
+        assert_eq!(rfind("a/b/c", "/"), 3);
+        assert_eq!(rfind("abab", "ab"), 2);
+        assert_eq!(rfind("abc", "z"), -1);
+        assert_eq!(rfind("ab", "abc"), -1);
+    }
+
+    #[test]
+    fn test_find_from_repeated() {
+        assert_eq!(find_from("abab", "ab", 0), 0);
+        assert_eq!(find_from("abab", "ab", 1), 2);
+        assert_eq!(find_from("abab", "ab", 2), 2);
+        assert_eq!(find_from("abab", "ab", 3), -1);
+        assert_eq!(find_from("ab", "abc", 0), -1);
+    }
+
+    #[test]
+    fn test_find_from_start_out_of_range() {
+        assert_eq!(find_from("abc", "a", -5), 0);
+        assert_eq!(find_from("abc", "", 3), 3);
+        assert_eq!(find_from("abc", "a", 4), -1);
+        assert_eq!(find_from("é-é", "é", 1), -1);
+        assert_eq!(find_from("é-é", "é", 2), 3);
+    }
+
+    // ── split_limit ─────────────────────────────────────────
+    #[test]
+    fn test_split_limit() {
+        let line = "name: description with: colons";
+        assert_eq!(split_limit(line, ": ", 1), vec![line]);
+        assert_eq!(split_limit(line, ": ", 2), vec!["name", "description with: colons"]);
+        assert_eq!(split_limit(line, ": ", 10), vec!["name", "description with", "colons"]);
+        assert_eq!(split_limit(line, ": ", 0), split(line, ": "));
+        assert_eq!(split_limit(String::from("a,b,c"), ",", -1), vec!["a", "b", "c"]);
+    }
+
+    // ── try_parse_int / try_parse_float ─────────────────────
+    #[test]
+    fn test_try_parse_int_whitespace_and_signs() {
+        assert_eq!(try_parse_int("  42  "), (true, 42));
+        assert_eq!(try_parse_int("\t-7\n"), (true, -7));
+        assert_eq!(try_parse_int("+7"), (true, 7));
+        assert_eq!(try_parse_int("- 7"), (false, 0));
+        assert_eq!(try_parse_int("--7"), (false, 0));
+    }
+
+    #[test]
+    fn test_try_parse_int_rejects_garbage_and_overflow() {
+        assert_eq!(try_parse_int(""), (false, 0));
+        assert_eq!(try_parse_int("   "), (false, 0));
+        assert_eq!(try_parse_int("12abc"), (false, 0));
+        assert_eq!(try_parse_int("1e3"), (false, 0));
+        assert_eq!(try_parse_int("3.5"), (false, 0));
+        assert_eq!(try_parse_int("2147483647"), (true, i32::MAX));
+        assert_eq!(try_parse_int("-2147483648"), (true, i32::MIN));
+        assert_eq!(try_parse_int("2147483648"), (false, 0));
+        assert_eq!(try_parse_int("-2147483649"), (false, 0));
+        assert_eq!(try_parse_int(String::from("0")), (true, 0));
+    }
+
+    #[test]
+    fn test_try_parse_float() {
+        assert_eq!(try_parse_float(" -3.5 "), (true, -3.5));
+        assert_eq!(try_parse_float("+2"), (true, 2.0));
+        assert_eq!(try_parse_float("1e3"), (true, 1000.0));
+        assert_eq!(try_parse_float(""), (false, 0.0));
+        assert_eq!(try_parse_float("12abc"), (false, 0.0));
+        assert_eq!(try_parse_float("1.5.2"), (false, 0.0));
+    }
+
+    #[test]
+    fn test_try_parse_float_inf_nan() {
+        assert_eq!(try_parse_float("inf"), (true, f32::INFINITY));
+        assert_eq!(try_parse_float("-inf"), (true, f32::NEG_INFINITY));
+        let (ok, x) = try_parse_float("nan");
+        assert!(ok && x.is_nan());
+        assert_eq!(try_parse_float("nanx"), (false, 0.0));
+    }
+
+    // ── parse_int_radix / parse_int_auto ────────────────────
+    #[test]
+    fn test_try_parse_int_radix() {
+        assert_eq!(try_parse_int_radix("1010", 2), (true, 10));
+        assert_eq!(try_parse_int_radix("17", 8), (true, 15));
+        assert_eq!(try_parse_int_radix(" ff ", 16), (true, 255));
+        assert_eq!(try_parse_int_radix("FF", 16), (true, 255));
+        assert_eq!(try_parse_int_radix("zz", 36), (true, 1295));
+        assert_eq!(try_parse_int_radix("-101", 2), (true, -5));
+        assert_eq!(parse_int_radix("77", 8), 63);
+    }
+
+    #[test]
+    fn test_try_parse_int_radix_invalid() {
+        assert_eq!(try_parse_int_radix("102", 2), (false, 0));
+        assert_eq!(try_parse_int_radix("8", 8), (false, 0));
+        assert_eq!(try_parse_int_radix("g", 16), (false, 0));
+        assert_eq!(try_parse_int_radix("0x10", 16), (false, 0));
+        assert_eq!(try_parse_int_radix("1", 1), (false, 0));
+        assert_eq!(try_parse_int_radix("1", 37), (false, 0));
+        assert_eq!(try_parse_int_radix("1", -2), (false, 0));
+        assert_eq!(parse_int_radix("1", 0), 0);
+    }
+
+    #[test]
+    fn test_try_parse_int_auto_prefixes() {
+        assert_eq!(try_parse_int_auto("0xff"), (true, 255));
+        assert_eq!(try_parse_int_auto("0XFF"), (true, 255));
+        assert_eq!(try_parse_int_auto("0o17"), (true, 15));
+        assert_eq!(try_parse_int_auto("-0b101"), (true, -5));
+        assert_eq!(try_parse_int_auto("+0x10"), (true, 16));
+        assert_eq!(try_parse_int_auto(" 99 "), (true, 99));
+        assert_eq!(try_parse_int_auto("-0x80000000"), (true, i32::MIN));
+        assert_eq!(parse_int_auto("0B11"), 3);
+    }
+
+    #[test]
+    fn test_try_parse_int_auto_invalid() {
+        assert_eq!(try_parse_int_auto("0x"), (false, 0));
+        assert_eq!(try_parse_int_auto("-0b"), (false, 0));
+        assert_eq!(try_parse_int_auto("0x-5"), (false, 0));
+        assert_eq!(try_parse_int_auto("0b102"), (false, 0));
+        assert_eq!(try_parse_int_auto("0x80000000"), (false, 0));
+        assert_eq!(try_parse_int_auto(""), (false, 0));
+        assert_eq!(parse_int_auto("abc"), 0);
+    }
+
+    // ── substr / substr_chars ───────────────────────────────
+    #[test]
+    fn test_substr_offset_inside_char_snaps_back() {
+        // "héllo": 'é' occupies bytes 1..3.
+        assert_eq!(substr("héllo", 0, 2), "h");
+        assert_eq!(substr("héllo", 2, 4), "él");
+        assert_eq!(substr("héllo", 1, 3), "é");
+        assert_eq!(substr("日本語", 1, 7), "日本");
+        assert_eq!(substr("日本語", -2, 9), "語");
+        assert_eq!(substr("héllo", -5, 3), "é");
+    }
+
+    #[test]
+    fn test_substr_ranges() {
+        assert_eq!(substr("hello", 1, -1), "ell");
+        assert_eq!(substr("hello", 3, 1), "");
+        assert_eq!(substr("hello", -100, 2), "he");
+        assert_eq!(substr("hello", 2, 100), "llo");
+        assert_eq!(substr("hello", 100, 200), "");
+        assert_eq!(substr(String::from(""), 0, 1), "");
+    }
+
+    #[test]
+    fn test_substr_chars_multibyte() {
+        assert_eq!(substr_chars("héllo", 0, 2), "hé");
+        assert_eq!(substr_chars("héllo", 1, -1), "éll");
+        assert_eq!(substr_chars("日本語", 1, 3), "本語");
+        assert_eq!(substr_chars("日本語テキスト", -3, -1), "キス");
+        assert_eq!(substr_chars("日本語", -2, 100), "本語");
+    }
+
+    #[test]
+    fn test_substr_chars_ranges() {
+        assert_eq!(substr_chars("日本語", 2, 1), "");
+        assert_eq!(substr_chars("日本語", -100, 1), "日");
+        assert_eq!(substr_chars("日本語", 5, 9), "");
+        assert_eq!(substr_chars("", -1, 1), "");
+    }
+}
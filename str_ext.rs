@@ -7,6 +7,18 @@
 //
 //   s := str_repeat(" ", 10)       // "          "
 //   s := str_pad_center("hi", 10)  // "    hi    "
+//   lines := str_wrap("a long node label", 8)  // ["a long", "node", "label"]
+//   s := str_truncate("very long label", 10)   // "very long…"
+//   s := str_title("flowchart layout")         // "Flowchart Layout"
+//   src := str_dedent(sample)                  // strip common indentation
+//   block := str_indent(body, "    ")          // prefix every line
+//   row := str_expand_tabs(line, 4)            // tabs -> spaces to next stop
+//   n := str_count("A --> B --> C", "-->")     // 2 (non-overlapping)
+//   d := str_levenshtein("kitten", "sitting")  // 3
+//   best, d := str_closest(typo, node_ids)     // ("", -1) if no candidates
+//   p := str_common_prefix(@["stage_build", "stage_test"])  // "stage_"
+//   kv := str_splitn("key: a: b", ": ", 1)     // ["key", "a: b"]
+//   row := str_overlay(row, label, 12)         // overwrite from column 12
 //
 // Used by: Canvas padding, node label centering.
 //
@@ -44,6 +56,354 @@ pub fn str_pad_center(s: impl AsRef<str>, width: i32) -> String {
     format!("{}{}{}", " ".repeat(left), s, " ".repeat(right))
 }
 
+/// Greedily wrap `s` into lines of at most `width` characters, breaking at
+/// whitespace (runs collapse to one space) and splitting any word longer
+/// than `width` mid-word. Empty input gives one empty line; width <= 0
+/// returns `s` unchanged as a single line.
+pub fn str_wrap(s: impl AsRef<str>, width: i32) -> Vec<String> {
+    let s = s.as_ref();
+    if width <= 0 {
+        return vec![s.to_string()];
+    }
+    let width = width as usize;
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_len = 0;
+    for word in s.split_whitespace() {
+        let word_len = word.chars().count();
+        if word_len > width {
+            if line_len > 0 {
+                lines.push(std::mem::take(&mut line));
+            }
+            let chars: Vec<char> = word.chars().collect();
+            let mut chunks = chars.chunks(width).peekable();
+            while let Some(chunk) = chunks.next() {
+                if chunks.peek().is_some() {
+                    lines.push(chunk.iter().collect());
+                } else {
+                    line = chunk.iter().collect();
+                    line_len = chunk.len();
+                }
+            }
+        } else if line_len == 0 {
+            line.push_str(word);
+            line_len = word_len;
+        } else if line_len + 1 + word_len <= width {
+            line.push(' ');
+            line.push_str(word);
+            line_len += 1 + word_len;
+        } else {
+            lines.push(std::mem::replace(&mut line, word.to_string()));
+            line_len = word_len;
+        }
+    }
+    if line_len > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+/// `str_wrap` with the lines joined by '\n'.
+pub fn str_wrap_joined(s: impl AsRef<str>, width: i32) -> String {
+    str_wrap(s, width).join("\n")
+}
+
+/// Shorten `s` to at most `max_width` characters, ending in "…" when
+/// anything was cut ("very long label" -> "very long…" at 10).
+/// Counts and cuts by characters, so multi-byte text is never split.
+/// max_width <= 0 returns "".
+pub fn str_truncate(s: impl AsRef<str>, max_width: i32) -> String {
+    truncate_with(s.as_ref(), max_width, "…")
+}
+
+/// `str_truncate` with an ASCII "..." ellipsis. Widths below 3 return as
+/// many dots as fit.
+pub fn str_truncate_ascii(s: impl AsRef<str>, max_width: i32) -> String {
+    truncate_with(s.as_ref(), max_width, "...")
+}
+
+fn truncate_with(s: &str, max_width: i32, ellipsis: &str) -> String {
+    let max = if max_width <= 0 { 0usize } else { max_width as usize };
+    if s.chars().count() <= max {
+        return s.to_string();
+    }
+    let ell_len = ellipsis.chars().count();
+    if max <= ell_len {
+        return ellipsis.chars().take(max).collect();
+    }
+    let mut out: String = s.chars().take(max - ell_len).collect();
+    out.push_str(ellipsis);
+    out
+}
+
+/// Uppercase the first character of every whitespace-separated word and
+/// lowercase the rest ("flowchart layout" -> "Flowchart Layout").
+/// Whitespace is copied through unchanged.
+pub fn str_title(s: impl AsRef<str>) -> String {
+    let mut out = String::new();
+    let mut at_word_start = true;
+    for c in s.as_ref().chars() {
+        if c.is_whitespace() {
+            out.push(c);
+            at_word_start = true;
+        } else if at_word_start {
+            out.extend(c.to_uppercase());
+            at_word_start = false;
+        } else {
+            out.extend(c.to_lowercase());
+        }
+    }
+    out
+}
+
+/// Uppercase the first character of `s` and lowercase the rest, like
+/// Python's str.capitalize. A leading space stays a space (" a" -> " a").
+pub fn str_capitalize(s: impl AsRef<str>) -> String {
+    let mut chars = s.as_ref().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+/// Remove the longest common leading whitespace from every non-blank line,
+/// like Python's textwrap.dedent. Tabs and spaces are distinct, so "\t" and
+/// "    " share no prefix. Blank (whitespace-only) lines do not affect the
+/// prefix and are kept exactly as they are; line endings are preserved.
+pub fn str_dedent(s: impl AsRef<str>) -> String {
+    let s = s.as_ref();
+    let is_blank = |line: &str| line.trim().is_empty();
+    let indent_of = |line: &str| -> usize {
+        line.len() - line.trim_start_matches([' ', '\t']).len()
+    };
+    let mut common: Option<&str> = None;
+    for line in s.split_inclusive('\n').filter(|l| !is_blank(l)) {
+        let indent = &line[..indent_of(line)];
+        common = Some(match common {
+            None => indent,
+            Some(prev) => {
+                let n = prev.bytes().zip(indent.bytes()).take_while(|(a, b)| a == b).count();
+                &prev[..n]
+            }
+        });
+    }
+    let cut = common.map_or(0, str::len);
+    s.split_inclusive('\n')
+        .map(|line| if is_blank(line) { line } else { &line[cut..] })
+        .collect()
+}
+
+/// Prepend `prefix` to every line of `s`, blank lines included.
+/// Line endings (\n, \r\n, or none on the last line) are kept exactly.
+pub fn str_indent(s: impl AsRef<str>, prefix: impl AsRef<str>) -> String {
+    indent_lines(s.as_ref(), prefix.as_ref(), false)
+}
+
+/// Like `str_indent`, but whitespace-only lines are left alone
+/// (the default behaviour of Python's textwrap.indent).
+pub fn str_indent_nonblank(s: impl AsRef<str>, prefix: impl AsRef<str>) -> String {
+    indent_lines(s.as_ref(), prefix.as_ref(), true)
+}
+
+fn indent_lines(s: &str, prefix: &str, skip_blank: bool) -> String {
+    let mut out = String::new();
+    for line in s.split_inclusive('\n') {
+        if !(skip_blank && line.trim().is_empty()) {
+            out.push_str(prefix);
+        }
+        out.push_str(line);
+    }
+    out
+}
+
+/// Replace each tab with spaces up to the next multiple of `tab_width`,
+/// like Python's str.expandtabs. Columns are counted in characters and
+/// reset after \n or \r. tab_width <= 0 deletes tabs.
+pub fn str_expand_tabs(s: impl AsRef<str>, tab_width: i32) -> String {
+    let mut out = String::new();
+    let mut col = 0usize;
+    for c in s.as_ref().chars() {
+        match c {
+            '\t' => {
+                if tab_width > 0 {
+                    let w = tab_width as usize;
+                    let n = w - col % w;
+                    out.push_str(&" ".repeat(n));
+                    col += n;
+                }
+            }
+            '\n' | '\r' => {
+                out.push(c);
+                col = 0;
+            }
+            _ => {
+                out.push(c);
+                col += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Count non-overlapping occurrences of `sub` in `s`, like Python's
+/// str.count. Returns 0 for an empty `sub`.
+pub fn str_count(s: impl AsRef<str>, sub: impl AsRef<str>) -> i32 {
+    let sub = sub.as_ref();
+    if sub.is_empty() {
+        return 0;
+    }
+    s.as_ref().matches(sub).count() as i32
+}
+
+/// Count occurrences of `sub` in `s`, allowing matches to overlap
+/// ("aaaa" contains "aa" 3 times). Returns 0 for an empty `sub`.
+pub fn str_count_overlapping(s: impl AsRef<str>, sub: impl AsRef<str>) -> i32 {
+    let (s, sub) = (s.as_ref(), sub.as_ref());
+    if sub.is_empty() {
+        return 0;
+    }
+    s.char_indices().filter(|(i, _)| s[*i..].starts_with(sub)).count() as i32
+}
+
+/// Levenshtein edit distance between `a` and `b`, counted in characters
+/// (insertions, deletions and substitutions each cost 1).
+/// O(len(a) * len(b)) time with a single rolling row of memory.
+pub fn str_levenshtein(a: impl AsRef<str>, b: impl AsRef<str>) -> i32 {
+    let a: Vec<char> = a.as_ref().chars().collect();
+    let b: Vec<char> = b.as_ref().chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diag = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let subst = diag + usize::from(ca != cb);
+            diag = row[j + 1];
+            row[j + 1] = subst.min(row[j] + 1).min(diag + 1);
+        }
+    }
+    row[b.len()] as i32
+}
+
+/// The candidate closest to `target` by `str_levenshtein`, with its
+/// distance, for "did you mean" suggestions. Ties go to the earliest
+/// candidate. Returns `("", -1)` for an empty candidate list.
+pub fn str_closest(target: impl AsRef<str>, candidates: Vec<String>) -> (String, i32) {
+    let target = target.as_ref();
+    let mut best = (String::new(), -1);
+    for c in candidates {
+        let d = str_levenshtein(target, &c);
+        if best.1 < 0 || d < best.1 {
+            best = (c, d);
+        }
+    }
+    best
+}
+
+/// Longest common prefix of all strings in `v`, compared by characters so
+/// multi-byte text is never split. "" for an empty list.
+pub fn str_common_prefix(v: Vec<String>) -> String {
+    let mut iter = v.into_iter();
+    let Some(first) = iter.next() else {
+        return String::new();
+    };
+    let mut n = first.chars().count();
+    for s in iter {
+        n = n.min(str_prefix_len(&first, s) as usize);
+    }
+    first.chars().take(n).collect()
+}
+
+/// Longest common suffix of all strings in `v`, compared by characters.
+/// "" for an empty list.
+pub fn str_common_suffix(v: Vec<String>) -> String {
+    let mut iter = v.into_iter();
+    let Some(first) = iter.next() else {
+        return String::new();
+    };
+    let len = first.chars().count();
+    let mut n = len;
+    for s in iter {
+        n = n.min(str_suffix_len(&first, s) as usize);
+    }
+    first.chars().skip(len - n).collect()
+}
+
+/// Number of leading characters `a` and `b` share.
+pub fn str_prefix_len(a: impl AsRef<str>, b: impl AsRef<str>) -> i32 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    a.chars().zip(b.chars()).take_while(|(x, y)| x == y).count() as i32
+}
+
+/// Number of trailing characters `a` and `b` share.
+pub fn str_suffix_len(a: impl AsRef<str>, b: impl AsRef<str>) -> i32 {
+    let (a, b) = (a.as_ref(), b.as_ref());
+    a.chars().rev().zip(b.chars().rev()).take_while(|(x, y)| x == y).count() as i32
+}
+
+/// Split `s` on `sep` at most `n` times from the left, like Python's
+/// `s.split(sep, n)`: the result has at most n+1 parts and the last part
+/// keeps the rest unsplit. n < 0 means unlimited; n == 0 returns `[s]`.
+/// Panics if `sep` is empty.
+pub fn str_splitn(s: impl AsRef<str>, sep: impl AsRef<str>, n: i32) -> Vec<String> {
+    let (s, sep) = (s.as_ref(), sep.as_ref());
+    assert!(!sep.is_empty(), "str_splitn: separator must not be empty");
+    if n < 0 {
+        return s.split(sep).map(String::from).collect();
+    }
+    s.splitn(n as usize + 1, sep).map(String::from).collect()
+}
+
+/// Split `s` on `sep` at most `n` times from the right, like Python's
+/// `s.rsplit(sep, n)`. Parts are returned in left-to-right order; the first
+/// part keeps the rest unsplit. n < 0 means unlimited.
+/// Panics if `sep` is empty.
+pub fn str_rsplitn(s: impl AsRef<str>, sep: impl AsRef<str>, n: i32) -> Vec<String> {
+    let (s, sep) = (s.as_ref(), sep.as_ref());
+    assert!(!sep.is_empty(), "str_rsplitn: separator must not be empty");
+    let mut parts: Vec<String> = if n < 0 {
+        s.rsplit(sep).map(String::from).collect()
+    } else {
+        s.rsplitn(n as usize + 1, sep).map(String::from).collect()
+    };
+    parts.reverse();
+    parts
+}
+
+/// Write `insert` over `base` starting at character index `col`, replacing
+/// the characters already there (canvas-style). `base` is padded with
+/// spaces if `col` or the end of `insert` lies past its end; negative `col`
+/// is treated as 0. Works on characters, so box-drawing text stays aligned.
+pub fn str_overlay(base: impl AsRef<str>, insert: impl AsRef<str>, col: i32) -> String {
+    let mut chars: Vec<char> = base.as_ref().chars().collect();
+    let col = if col <= 0 { 0usize } else { col as usize };
+    for (i, c) in insert.as_ref().chars().enumerate() {
+        let at = col + i;
+        if at >= chars.len() {
+            chars.resize(at, ' ');
+            chars.push(c);
+        } else {
+            chars[at] = c;
+        }
+    }
+    if chars.len() < col {
+        chars.resize(col, ' ');
+    }
+    chars.into_iter().collect()
+}
+
+/// Insert `insert` into `base` before character index `col`, shifting the
+/// rest right. `base` is padded with spaces if `col` lies past its end;
+/// negative `col` is treated as 0.
+pub fn str_insert_at(base: impl AsRef<str>, insert: impl AsRef<str>, col: i32) -> String {
+    let mut chars: Vec<char> = base.as_ref().chars().collect();
+    let col = if col <= 0 { 0usize } else { col as usize };
+    if chars.len() < col {
+        chars.resize(col, ' ');
+    }
+    chars.splice(col..col, insert.as_ref().chars());
+    chars.into_iter().collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -143,4 +503,483 @@ mod tests {
         assert_eq!(str_pad_center("hi".to_string(), 6), "  hi  ");
         assert_eq!(str_pad_center("".to_string(), 4), "    ");
     }
+
+    // ── str_wrap / str_wrap_joined ──────────────────────────
+
+    #[test]
+    fn test_str_wrap_words() {
+        assert_eq!(
+            str_wrap("the quick brown fox", 10),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn test_str_wrap_exact_fit() {
+        assert_eq!(str_wrap("abcde fghij", 5), vec!["abcde", "fghij"]);
+        assert_eq!(str_wrap("ab cd", 5), vec!["ab cd"]);
+    }
+
+    #[test]
+    fn test_str_wrap_long_unbreakable_token() {
+        assert_eq!(
+            str_wrap("a verylongidentifier b", 6),
+            vec!["a", "verylo", "ngiden", "tifier", "b"]
+        );
+    }
+
+    #[test]
+    fn test_str_wrap_never_exceeds_width() {
+        let text = "lorem ipsum dolor sit amet consectetur adipiscing elit";
+        for width in 1..20 {
+            for line in str_wrap(text, width) {
+                assert!(line.chars().count() <= width as usize, "{:?} at {}", line, width);
+            }
+        }
+    }
+
+    #[test]
+    fn test_str_wrap_counts_chars_not_bytes() {
+        assert_eq!(str_wrap("héllo wörld", 5), vec!["héllo", "wörld"]);
+    }
+
+    #[test]
+    fn test_str_wrap_empty_and_non_positive_width() {
+        assert_eq!(str_wrap("", 10), vec![""]);
+        assert_eq!(str_wrap("   ", 10), vec![""]);
+        assert_eq!(str_wrap("a b c", 0), vec!["a b c"]);
+        assert_eq!(str_wrap("a b c", -3), vec!["a b c"]);
+    }
+
+    #[test]
+    fn test_str_wrap_joined() {
+        assert_eq!(str_wrap_joined("node label text", 10), "node label\ntext");
+        assert_eq!(str_wrap_joined("", 4), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_wrap_string_type() {
+        let label = String::from("Start Here");
+        assert_eq!(str_wrap(label, 5), vec!["Start", "Here"]);
+    }
+
+    // ── str_truncate / str_truncate_ascii ───────────────────
+
+    #[test]
+    fn test_str_truncate_fits_unchanged() {
+        assert_eq!(str_truncate("short", 5), "short");
+        assert_eq!(str_truncate("short", 10), "short");
+        assert_eq!(str_truncate_ascii("", 3), "");
+    }
+
+    #[test]
+    fn test_str_truncate_appends_ellipsis() {
+        let out = str_truncate("very long label", 10);
+        assert_eq!(out, "very long…");
+        assert_eq!(out.chars().count(), 10);
+        assert_eq!(str_truncate_ascii("very long label", 10), "very lo...");
+    }
+
+    #[test]
+    fn test_str_truncate_cjk() {
+        let out = str_truncate("長いラベルです", 4);
+        assert_eq!(out, "長いラ…");
+        assert_eq!(out.chars().count(), 4);
+        assert_eq!(str_truncate_ascii("長いラベルです", 5), "長い...");
+    }
+
+    #[test]
+    fn test_str_truncate_tiny_widths() {
+        assert_eq!(str_truncate("abc", 1), "…");
+        assert_eq!(str_truncate("abc", 0), "");
+        assert_eq!(str_truncate("abc", -1), "");
+        assert_eq!(str_truncate_ascii("abcdef", 2), "..");
+        assert_eq!(str_truncate_ascii("abcdef", 3), "...");
+        assert_eq!(str_truncate_ascii("abcdef", 4), "a...");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_truncate_string_type() {
+        let label = String::from("abcdefgh");
+        assert_eq!(str_truncate(label, 4), "abc…");
+    }
+
+    // ── str_title / str_capitalize ──────────────────────────
+
+    #[test]
+    fn test_str_title() {
+        assert_eq!(str_title("flowchart layout"), "Flowchart Layout");
+        assert_eq!(str_title("hello  world"), "Hello  World");
+        assert_eq!(str_title(" leading"), " Leading");
+        assert_eq!(str_title("trailing "), "Trailing ");
+        assert_eq!(str_title("mIXED cASE"), "Mixed Case");
+        assert_eq!(str_title("a\tb\nc"), "A\tB\nC");
+        assert_eq!(str_title(""), "");
+    }
+
+    #[test]
+    fn test_str_capitalize() {
+        assert_eq!(str_capitalize("hello  world"), "Hello  world");
+        assert_eq!(str_capitalize("ÉCOLE"), "École");
+        assert_eq!(str_capitalize(" leading"), " leading");
+        assert_eq!(str_capitalize("x"), "X");
+        assert_eq!(str_capitalize(""), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_title_string_type() {
+        let s = String::from("node label");
+        assert_eq!(str_title(s.clone()), "Node Label");
+        assert_eq!(str_capitalize(s), "Node label");
+    }
+
+    // ── str_dedent ──────────────────────────────────────────
+
+    #[test]
+    fn test_str_dedent_spaces() {
+        let src = "    graph TD\n      A --> B\n    B --> C\n";
+        assert_eq!(str_dedent(src), "graph TD\n  A --> B\nB --> C\n");
+    }
+
+    #[test]
+    fn test_str_dedent_blank_lines_ignored_and_kept() {
+        let src = "    a\n\n  \n    b";
+        assert_eq!(str_dedent(src), "a\n\n  \nb");
+    }
+
+    #[test]
+    fn test_str_dedent_tabs_and_spaces_distinct() {
+        assert_eq!(str_dedent("\ta\n    b\n"), "\ta\n    b\n");
+        assert_eq!(str_dedent("\t\ta\n\tb\n"), "\ta\nb\n");
+        assert_eq!(str_dedent("\t  a\n\t b\n"), " a\nb\n");
+    }
+
+    #[test]
+    fn test_str_dedent_no_common_indent() {
+        assert_eq!(str_dedent("a\n  b"), "a\n  b");
+    }
+
+    #[test]
+    fn test_str_dedent_all_blank_unchanged() {
+        assert_eq!(str_dedent("  \n\t\n"), "  \n\t\n");
+        assert_eq!(str_dedent(""), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_dedent_string_type() {
+        let src = String::from("  x\n  y");
+        assert_eq!(str_dedent(src), "x\ny");
+    }
+
+    // ── str_indent / str_indent_nonblank ────────────────────
+
+    #[test]
+    fn test_str_indent_trailing_newline() {
+        assert_eq!(str_indent("a\nb\n", "    "), "    a\n    b\n");
+    }
+
+    #[test]
+    fn test_str_indent_no_trailing_newline() {
+        assert_eq!(str_indent("a\nb", "> "), "> a\n> b");
+    }
+
+    #[test]
+    fn test_str_indent_crlf_preserved() {
+        assert_eq!(str_indent("a\r\nb\r\n", "  "), "  a\r\n  b\r\n");
+    }
+
+    #[test]
+    fn test_str_indent_blank_lines() {
+        assert_eq!(str_indent("a\n\nb\n", "  "), "  a\n  \n  b\n");
+        assert_eq!(str_indent_nonblank("a\n\n \nb\n", "  "), "  a\n\n \n  b\n");
+    }
+
+    #[test]
+    fn test_str_indent_empty() {
+        assert_eq!(str_indent("", "  "), "");
+        assert_eq!(str_indent_nonblank("", "  "), "");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_indent_string_type() {
+        let block = String::from("x\ny");
+        let prefix = String::from("\t");
+        assert_eq!(str_indent(block, prefix), "\tx\n\ty");
+    }
+
+    // ── str_expand_tabs ─────────────────────────────────────
+
+    #[test]
+    fn test_str_expand_tabs_column_zero() {
+        assert_eq!(str_expand_tabs("\tx", 4), "    x");
+        assert_eq!(str_expand_tabs("\tx", 8), "        x");
+    }
+
+    #[test]
+    fn test_str_expand_tabs_mid_line() {
+        assert_eq!(str_expand_tabs("ab\tc", 4), "ab  c");
+        assert_eq!(str_expand_tabs("abcd\te", 4), "abcd    e");
+    }
+
+    #[test]
+    fn test_str_expand_tabs_after_multibyte() {
+        assert_eq!(str_expand_tabs("é─\tx", 4), "é─  x");
+    }
+
+    #[test]
+    fn test_str_expand_tabs_consecutive() {
+        assert_eq!(str_expand_tabs("a\t\tb", 4), "a       b");
+    }
+
+    #[test]
+    fn test_str_expand_tabs_per_line() {
+        assert_eq!(str_expand_tabs("abc\n\tx", 4), "abc\n    x");
+    }
+
+    #[test]
+    fn test_str_expand_tabs_non_positive_width_deletes() {
+        assert_eq!(str_expand_tabs("a\tb", 0), "ab");
+        assert_eq!(str_expand_tabs("a\tb", -2), "ab");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_expand_tabs_string_type() {
+        let line = String::from("\tnode");
+        assert_eq!(str_expand_tabs(line, 2), "  node");
+    }
+
+    // ── str_count / str_count_overlapping ───────────────────
+
+    #[test]
+    fn test_str_count_non_overlapping() {
+        assert_eq!(str_count("aaaa", "aa"), 2);
+        assert_eq!(str_count("A --> B --> C", "-->"), 2);
+        assert_eq!(str_count("abc", "x"), 0);
+        assert_eq!(str_count("ab", "abc"), 0);
+    }
+
+    #[test]
+    fn test_str_count_overlapping() {
+        assert_eq!(str_count_overlapping("aaaa", "aa"), 3);
+        assert_eq!(str_count_overlapping("ababa", "aba"), 2);
+        assert_eq!(str_count_overlapping("éée", "é"), 2);
+        assert_eq!(str_count_overlapping("", "a"), 0);
+    }
+
+    #[test]
+    fn test_str_count_empty_sub() {
+        assert_eq!(str_count("abc", ""), 0);
+        assert_eq!(str_count_overlapping("abc", ""), 0);
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_count_string_type() {
+        let line = String::from("a-b-c");
+        let sep = String::from("-");
+        assert_eq!(str_count(line, sep), 2);
+    }
+
+    // ── str_levenshtein / str_closest ───────────────────────
+
+    #[test]
+    fn test_str_levenshtein_classic() {
+        assert_eq!(str_levenshtein("kitten", "sitting"), 3);
+        assert_eq!(str_levenshtein("flaw", "lawn"), 2);
+    }
+
+    #[test]
+    fn test_str_levenshtein_identical_and_empty() {
+        assert_eq!(str_levenshtein("same", "same"), 0);
+        assert_eq!(str_levenshtein("", ""), 0);
+        assert_eq!(str_levenshtein("", "abc"), 3);
+        assert_eq!(str_levenshtein("abc", ""), 3);
+    }
+
+    #[test]
+    fn test_str_levenshtein_unicode_by_chars() {
+        assert_eq!(str_levenshtein("café", "cafe"), 1);
+        assert_eq!(str_levenshtein("日本語", "日本"), 1);
+    }
+
+    #[test]
+    fn test_str_closest() {
+        let ids = vec!["start".to_string(), "stop".to_string(), "end".to_string()];
+        assert_eq!(str_closest("strat", ids.clone()), ("start".to_string(), 2));
+        assert_eq!(str_closest("end", ids), ("end".to_string(), 0));
+    }
+
+    #[test]
+    fn test_str_closest_tie_and_empty() {
+        let ids = vec!["ab".to_string(), "ba".to_string()];
+        assert_eq!(str_closest("aa", ids), ("ab".to_string(), 1));
+        assert_eq!(str_closest("x", vec![]), (String::new(), -1));
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_levenshtein_string_type() {
+        let a = String::from("node");
+        let b = String::from("nodes");
+        assert_eq!(str_levenshtein(a, b), 1);
+    }
+
+    // ── str_common_prefix / str_common_suffix ───────────────
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_str_common_prefix() {
+        assert_eq!(str_common_prefix(strings(&["stage_build", "stage_test"])), "stage_");
+        assert_eq!(str_common_prefix(strings(&["abc", "xyz"])), "");
+    }
+
+    #[test]
+    fn test_str_common_prefix_element_is_prefix_of_others() {
+        assert_eq!(str_common_prefix(strings(&["stage_a", "stage", "stage_b"])), "stage");
+        assert_eq!(str_common_suffix(strings(&["x.rs", ".rs", "main.rs"])), ".rs");
+    }
+
+    #[test]
+    fn test_str_common_prefix_empty_and_single() {
+        assert_eq!(str_common_prefix(vec![]), "");
+        assert_eq!(str_common_suffix(vec![]), "");
+        assert_eq!(str_common_prefix(strings(&["only"])), "only");
+        assert_eq!(str_common_suffix(strings(&["only"])), "only");
+    }
+
+    #[test]
+    fn test_str_common_suffix() {
+        assert_eq!(str_common_suffix(strings(&["build_stage", "test_stage"])), "_stage");
+        assert_eq!(str_common_suffix(strings(&["a", "b"])), "");
+    }
+
+    #[test]
+    fn test_str_common_multibyte() {
+        // "é" and "è" share their first UTF-8 byte; chars must not be split.
+        assert_eq!(str_common_prefix(strings(&["aé", "aè"])), "a");
+        assert_eq!(str_common_suffix(strings(&["éz", "èz"])), "z");
+        assert_eq!(str_common_prefix(strings(&["日本語", "日本"])), "日本");
+    }
+
+    #[test]
+    fn test_str_prefix_suffix_len() {
+        assert_eq!(str_prefix_len("stage_build", "stage_test"), 6);
+        assert_eq!(str_prefix_len("", "abc"), 0);
+        assert_eq!(str_suffix_len("é_x", "è_x"), 2);
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_prefix_len_string_type() {
+        let a = String::from("node_1");
+        let b = String::from("node_2");
+        assert_eq!(str_prefix_len(a, b), 5);
+    }
+
+    // ── str_splitn / str_rsplitn ────────────────────────────
+
+    #[test]
+    fn test_str_splitn_once() {
+        assert_eq!(
+            str_splitn("key: value: with: colons", ": ", 1),
+            vec!["key", "value: with: colons"]
+        );
+        assert_eq!(str_splitn("a:b:c:d", ":", 1), vec!["a", "b:c:d"]);
+    }
+
+    #[test]
+    fn test_str_splitn_zero_and_unlimited() {
+        assert_eq!(str_splitn("a:b:c", ":", 0), vec!["a:b:c"]);
+        assert_eq!(str_splitn("a:b:c", ":", -1), vec!["a", "b", "c"]);
+        assert_eq!(str_splitn("a:b:c", ":", 10), vec!["a", "b", "c"]);
+        assert_eq!(str_splitn("", ":", 1), vec![""]);
+    }
+
+    #[test]
+    fn test_str_rsplitn_keeps_rightmost_split() {
+        assert_eq!(str_rsplitn("a:b:c:d", ":", 1), vec!["a:b:c", "d"]);
+        assert_eq!(str_rsplitn("a:b:c:d", ":", 2), vec!["a:b", "c", "d"]);
+        assert_eq!(str_rsplitn("a:b:c", ":", 0), vec!["a:b:c"]);
+        assert_eq!(str_rsplitn("a:b:c", ":", -1), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "separator must not be empty")]
+    fn test_str_splitn_empty_sep_panics() {
+        str_splitn("abc", "", 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "separator must not be empty")]
+    fn test_str_rsplitn_empty_sep_panics() {
+        str_rsplitn("abc", "", 1);
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_splitn_string_type() {
+        let line = String::from("A --> B --> C");
+        let sep = String::from(" --> ");
+        assert_eq!(str_splitn(line, sep, 1), vec!["A", "B --> C"]);
+    }
+
+    // ── str_overlay / str_insert_at ─────────────────────────
+
+    #[test]
+    fn test_str_overlay_inside() {
+        assert_eq!(str_overlay("..........", "abc", 2), "..abc.....");
+        assert_eq!(str_overlay("abc", "X", 0), "Xbc");
+    }
+
+    #[test]
+    fn test_str_overlay_past_end() {
+        assert_eq!(str_overlay("abc", "XYZ", 2), "abXYZ");
+        assert_eq!(str_overlay("ab", "X", 4), "ab  X");
+        assert_eq!(str_overlay("ab", "", 4), "ab  ");
+    }
+
+    #[test]
+    fn test_str_overlay_empty_base() {
+        assert_eq!(str_overlay("", "hi", 0), "hi");
+        assert_eq!(str_overlay("", "hi", 3), "   hi");
+    }
+
+    #[test]
+    fn test_str_overlay_negative_col() {
+        assert_eq!(str_overlay("abc", "X", -5), "Xbc");
+    }
+
+    #[test]
+    fn test_str_overlay_box_drawing_chars() {
+        let row = "│        │";
+        let out = str_overlay(row, "Start", 2);
+        assert_eq!(out, "│ Start  │");
+        assert_eq!(out.chars().count(), row.chars().count());
+    }
+
+    #[test]
+    fn test_str_insert_at() {
+        assert_eq!(str_insert_at("abcd", "XY", 2), "abXYcd");
+        assert_eq!(str_insert_at("abc", "X", -1), "Xabc");
+        assert_eq!(str_insert_at("ab", "X", 4), "ab  X");
+        assert_eq!(str_insert_at("", "X", 0), "X");
+        assert_eq!(str_insert_at("─┼─", "│", 1), "─│┼─");
+    }
+
+    // Verify String type works (as emitted by homunc codegen)
+    #[test]
+    fn test_str_overlay_string_type() {
+        let row = String::from("    ");
+        let label = String::from("AB");
+        assert_eq!(str_overlay(row, label, 1), " AB ");
+    }
 }